@@ -0,0 +1,125 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator which counts allocations of the system allocator once `enable`d
+///
+/// Install it in the executable with `#[global_allocator] static ALLOCATOR: Counting = Counting;`.
+/// The counters are shared by all threads, so measurements are only meaningful when
+/// a single thread allocates at a time.
+pub struct Counting;
+
+impl Counting {
+    fn allocated(size: usize) {
+        if ENABLED.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED.fetch_add(size, Ordering::Relaxed);
+            let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+            PEAK.fetch_max(live, Ordering::Relaxed);
+        }
+    }
+
+    fn freed(size: usize) {
+        if ENABLED.load(Ordering::Relaxed) {
+            // memory allocated before enabling may be freed afterwards
+            let _ = LIVE.fetch_update(
+                Ordering::Relaxed, Ordering::Relaxed, |live| Some(live.saturating_sub(size))
+            );
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() {
+            Counting::allocated(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc_zeroed(layout);
+        if !pointer.is_null() {
+            Counting::allocated(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        Counting::freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let moved = System.realloc(pointer, layout, new_size);
+        if !moved.is_null() {
+            Counting::freed(layout.size());
+            Counting::allocated(new_size);
+        }
+        moved
+    }
+}
+
+/// Start counting allocations of the `Counting` allocator, if it is installed
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether allocations are counted
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Allocations made while running some code
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, including reallocations
+    pub allocations: usize,
+    /// Total bytes requested by all allocations
+    pub bytes: usize,
+    /// Most bytes live at once beyond those live before, including memory never freed
+    pub peak: usize,
+}
+
+/// Run `code` and count its allocations, if counting is `enable`d
+///
+/// Measurements may be nested; the peak of the inner code counts for the outer code as well.
+pub fn measure<R, F: FnOnce() -> R>(code: F) -> (R, Option<AllocStats>) {
+    if !enabled() {
+        return (code(), None)
+    }
+    let (allocations, bytes, live) = (
+        ALLOCATIONS.load(Ordering::Relaxed), ALLOCATED.load(Ordering::Relaxed), LIVE.load(Ordering::Relaxed)
+    );
+    let outer_peak = PEAK.swap(live, Ordering::Relaxed);
+    let result = code();
+    let peak = PEAK.fetch_max(outer_peak, Ordering::Relaxed);
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: ALLOCATED.load(Ordering::Relaxed) - bytes,
+        peak: peak.saturating_sub(live),
+    };
+    (result, Some(stats))
+}
+
+/// Format a number of bytes nicely
+pub fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut fractional = bytes as f64 / 1024.0;
+    for symbol in ["KiB", "MiB"].iter() {
+        if fractional < 1024.0 {
+            return format!("{:.2} {}", fractional, symbol);
+        }
+        fractional /= 1024.0;
+    }
+    format!("{:.2} GiB", fractional)
+}
@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use crate::error::AocError;
+use crate::log;
+use crate::runner::{execute, format_duration, print_answers, print_allocations, DayResult, InputSource};
+use crate::solution::{Part, Solver};
+
+
+/// Summary statistics of repeated timings
+#[derive(Debug, Clone)]
+pub struct Statistics {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Statistics {
+    /// Compute the statistics of some `samples`, which must not be empty
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let runs = sorted.len();
+        let median = if runs.is_multiple_of(2) {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        } else {
+            sorted[runs / 2]
+        };
+        let mean = sorted.iter().map(|sample| sample.as_secs_f64()).sum::<f64>() / runs as f64;
+        let variance = sorted.iter().map(
+            |sample| (sample.as_secs_f64() - mean).powi(2)
+        ).sum::<f64>() / runs as f64;
+        Statistics {
+            runs,
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Timing statistics of each phase of repeatedly running a day
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    pub parse: Statistics,
+    pub parts: Vec<(Part, Statistics)>,
+    /// Statistics of parsing and all parts together
+    pub total: Statistics,
+}
+
+/// Number of untimed runs before taking `runs` samples
+fn warmup_runs(runs: usize) -> usize {
+    (runs / 10).max(1)
+}
+
+/// Repeatedly run the `parts` of a `solver`, collecting timing statistics per phase
+///
+/// The input is read from its `source` only once, so that timings do not include I/O.
+/// The timings of the result are the medians of all `runs`.
+pub fn bench_solver(
+    solver: &dyn Solver, parts: &[Part], source: &InputSource, runs: usize
+) -> Result<(DayResult, BenchResult), AocError> {
+    let raw = source.read(solver.day())?;
+    let execute = |raw: &[u8]| execute(solver, parts, &mut &raw[..]).map_err(
+        |error| error.in_file(&source.name(solver.day()))
+    );
+    // only the first run shows the messages of the solver, rather than every one of the same
+    execute(&raw)?;
+    let (mut result, samples) = log::quietly(|| {
+        for _ in 1..warmup_runs(runs) {
+            execute(&raw)?;
+        }
+        let result = execute(&raw)?;
+        let mut samples = vec![result.clone()];
+        for _ in 1..runs {
+            samples.push(execute(&raw)?);
+        }
+        Ok::<_, AocError>((result, samples))
+    })?;
+    let statistics = |phase: &dyn Fn(&DayResult) -> Duration| Statistics::from_samples(
+        &samples.iter().map(phase).collect::<Vec<_>>()
+    );
+    let bench = BenchResult {
+        parse: statistics(&|sample| sample.parse),
+        parts: parts.iter().enumerate().map(
+            |(index, part)| (*part, statistics(&|sample| sample.parts[index].elapsed))
+        ).collect(),
+        total: statistics(&|sample| sample.elapsed()),
+    };
+    result.runs = runs;
+    result.parse = bench.parse.median;
+    for (part, (_, statistics)) in result.parts.iter_mut().zip(&bench.parts) {
+        part.elapsed = statistics.median;
+    }
+    Ok((result, bench))
+}
+
+/// Print the answers of a `result` and the timing statistics of its `bench`mark
+pub fn print_bench(result: &DayResult, bench: &BenchResult) {
+    print_answers(result);
+    print_statistics("Parse", &bench.parse);
+    for (part, statistics) in &bench.parts {
+        print_statistics(&format!("Part {}", part.number()), statistics);
+    }
+    print_statistics("Total", &bench.total);
+    print_allocations(result);
+}
+
+fn print_statistics(phase: &str, statistics: &Statistics) {
+    println!(
+        "[> {:<6} Min {} | Median {} | Mean {} ± {} over {} runs <]",
+        phase, format_duration(statistics.min), format_duration(statistics.median),
+        format_duration(statistics.mean), format_duration(statistics.stddev), statistics.runs,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(|&sample| Duration::from_millis(sample)).collect()
+    }
+
+    /// Whether `actual` is within a microsecond of `expected`, as the mean and deviation are computed in floats
+    fn close(actual: Duration, expected: Duration) -> bool {
+        actual.max(expected) - actual.min(expected) < Duration::from_micros(1)
+    }
+
+    #[test]
+    fn odd_number_of_samples() {
+        let statistics = Statistics::from_samples(&millis(&[3, 1, 2]));
+        assert_eq!(statistics.runs, 3);
+        assert_eq!(statistics.min, Duration::from_millis(1));
+        assert_eq!(statistics.median, Duration::from_millis(2));
+        assert!(close(statistics.mean, Duration::from_millis(2)));
+    }
+
+    #[test]
+    fn even_number_of_samples() {
+        let statistics = Statistics::from_samples(&millis(&[9, 4, 2, 5, 4, 7, 4, 5]));
+        assert_eq!(statistics.runs, 8);
+        assert_eq!(statistics.min, Duration::from_millis(2));
+        assert_eq!(statistics.median, Duration::from_micros(4500));
+        assert!(close(statistics.mean, Duration::from_millis(5)));
+        assert!(close(statistics.stddev, Duration::from_millis(2)));
+    }
+
+    #[test]
+    fn single_sample() {
+        let statistics = Statistics::from_samples(&millis(&[6]));
+        assert_eq!((statistics.min, statistics.median), (Duration::from_millis(6), Duration::from_millis(6)));
+        assert!(close(statistics.mean, Duration::from_millis(6)));
+        assert_eq!(statistics.stddev, Duration::ZERO);
+    }
+}
@@ -0,0 +1,380 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::{Section, Value};
+use crate::log::Level;
+use crate::pool;
+use crate::selection::Selection;
+use crate::solution::Part;
+use crate::runner::InputSource;
+
+pub const USAGE: &str = "\
+Usage: aoc2020_rust [OPTIONS] [DAYS]
+       aoc2020_rust verify [OPTIONS] [DAYS]
+       aoc2020_rust compare [OPTIONS] [DAYS]
+       aoc2020_rust report [OPTIONS] [DAYS]
+       aoc2020_rust new-day DAY
+       aoc2020_rust fetch DAY
+       aoc2020_rust submit [OPTIONS] DAY PART
+       aoc2020_rust repl [OPTIONS] DAY
+       aoc2020_rust generate [OPTIONS] DAY
+
+Commands:
+  (none)   run the days and show their answers
+  verify   run the days and compare their answers to the expected answers
+  compare  compare the latest benchmark timings of the days to their best earlier timings
+  report   run the days and write a report of their answers, timings and allocations
+  new-day  add the module, registration and data files for a new DAY of the year
+  fetch    download the input of DAY to dayN.txt in the data directory, unless already
+           there; needs the session token in AOC_SESSION and the plain HTTP server, such as
+           a local proxy, in AOC_BASE_URL
+  submit   solve PART of DAY and submit the answer, like fetch; answers known to be
+           wrong are not submitted again, using the log submissions.txt in the data directory
+  repl     parse the input of DAY once and read commands from stdin to explore it,
+           such as part1 or the commands of the day listed by help
+  generate write a random well-formed input of DAY to stdout, about as large as a real
+           input unless --size is given
+
+DAYS selects the days and parts to run, by default the latest day, as a comma separated list of
+  all    every day with a solution
+  latest the last day with a solution
+  7      a single day
+  3-9    an inclusive range of days
+  7:2    only the given part of a day or range, such as 1-5:1
+
+Options:
+  --config PATH  read defaults of options and per-day parameters from PATH instead of aoc.toml
+  --year YEAR    run the days of YEAR instead of the latest year with a solution
+  --data DIR     read inputs and other data from DIR/YEAR instead of data/YEAR
+  --input PATH   read the input of a single day from PATH, or stdin for -
+  --inputs DIR   run a single day on every file in DIR and compare the answers
+  --bench N      time N runs after some warmup runs and show statistics,
+                 and add the median timings to the history
+  --format FMT   show results as human readable text or as json
+  --jobs N       run up to N days at once; defaults to the number of cores,
+                 or to 1 for --bench
+  --answers PATH read expected answers from PATH instead of answers.txt in the data directory
+  --history PATH keep benchmark timings in PATH instead of history.txt in the data directory
+  --threshold P  let compare flag timings more than P percent slower, by default 10
+  --timeout SECS give up on a day after SECS seconds, such as 0.5, and go on with the others
+  --alloc-stats  count allocations, bytes and peak live bytes per phase;
+                 runs the days one at a time
+  --output PATH  write the report to PATH instead of report.md, as HTML for a .html PATH
+  --watch        run a single day again whenever its input or example input changes
+  --size N       let generate write N items, such as lines, passports or rules
+  --seed N       let generate use the random seed N instead of a new one each time
+  --planted PATH let generate write the answers it knows to PATH, for use with --answers
+  -v, -vv        show intermediate facts of the solvers on stderr, or with -vv every step
+  -h, --help     show this help
+
+The [runner] table of the configuration may set the default DAYS as day, the session token
+as session, the server as base_url, and any of the options year, data, jobs, timeout, format,
+answers, history, threshold and output. Tables such as [y2020.day9] set parameters of a day.
+";
+
+/// What to do with the selected days
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Run,
+    Verify,
+    /// Compare benchmark timings to the history
+    Compare,
+    /// Write a report of the results
+    Report,
+    /// Scaffold a new day
+    NewDay(u32),
+    /// Download the input of a day
+    Fetch(u32),
+    /// Solve and submit a part of a day
+    Submit(u32, Part),
+    /// Explore the parsed input of a day interactively
+    Repl(u32),
+    /// Write a random input of a day
+    Generate(u32),
+}
+
+/// Options of the command line interface
+#[derive(Debug)]
+pub struct Options {
+    pub command: Command,
+    pub selection: Selection,
+    /// Year of the advent calendar to run
+    pub year: u32,
+    /// Directory of inputs, answers and other data of the year
+    pub data: PathBuf,
+    /// Where to read the input from
+    pub input: InputSource,
+    /// Directory of several inputs to run on, if any
+    pub inputs: Option<PathBuf>,
+    /// Number of timed runs for benchmarking, if any
+    pub bench: Option<usize>,
+    /// Number of days to run at once, if not the default
+    pub jobs: Option<usize>,
+    /// Time after which to give up on a day, if any
+    pub timeout: Option<Duration>,
+    /// Whether to run again whenever the input changes
+    pub watch: bool,
+    /// Whether to count allocations
+    pub alloc_stats: bool,
+    pub format: Format,
+    /// File of expected answers for verification
+    pub answers: PathBuf,
+    /// File of benchmark timings
+    pub history: PathBuf,
+    /// File to write a report to
+    pub output: PathBuf,
+    /// Percentage by which timings may get slower before being flagged
+    pub threshold: f64,
+    /// Session token for the puzzle server, if configured
+    pub session: Option<String>,
+    /// Base URL of the puzzle server, if configured
+    pub base_url: Option<String>,
+    /// Number of items of a generated input, if not as many as a real input
+    pub size: Option<usize>,
+    /// Seed of the random generator, if not a new one
+    pub seed: Option<u64>,
+    /// Where to write the answers known for a generated input, if anywhere
+    pub planted: Option<PathBuf>,
+    /// Detail of the messages solvers show about their inner workings
+    pub log_level: Level,
+    /// Whether to only show the usage
+    pub help: bool,
+}
+
+impl Options {
+    /// Read the options from command line `arguments`, excluding the executable
+    ///
+    /// Options which are not given fall back to the configured `defaults` of the `[runner]` table.
+    pub fn from_args<I: IntoIterator<Item=String>>(
+        arguments: I, default_year: u32, defaults: &Section
+    ) -> Result<Self, String> {
+        defaults.expect_keys(&[
+            "day", "year", "data", "jobs", "timeout", "format", "answers", "history", "threshold", "output", "session",
+            "base_url",
+        ])?;
+        let (mut input, mut bench, mut jobs, mut help) = (None, None, None, false);
+        let (mut year, mut data, mut history, mut threshold, mut output) = (None, None, None, None, None);
+        let (mut watch, mut alloc_stats, mut timeout, mut inputs) = (false, false, None, None);
+        let (mut size, mut seed, mut planted, mut verbosity) = (None, None, None, 0);
+        let (mut format, mut answers) = (None, None);
+        let mut positional = vec![];
+        let mut arguments = arguments.into_iter();
+        while let Some(argument) = arguments.next() {
+            match argument.as_str() {
+                "-h" | "--help" => help = true,
+                // already read by `config_path`
+                "--config" => drop(value(&mut arguments, "--config")?),
+                "--year" => year = Some(positive(&mut arguments, "--year")?),
+                "--data" => data = Some(PathBuf::from(value(&mut arguments, "--data")?)),
+                "--input" => input = Some(value(&mut arguments, "--input")?.parse()?),
+                "--inputs" => inputs = Some(PathBuf::from(value(&mut arguments, "--inputs")?)),
+                "--format" => format = Some(value(&mut arguments, "--format")?.parse()?),
+                "--answers" => answers = Some(PathBuf::from(value(&mut arguments, "--answers")?)),
+                "--output" => output = Some(PathBuf::from(value(&mut arguments, "--output")?)),
+                "--history" => history = Some(PathBuf::from(value(&mut arguments, "--history")?)),
+                "--threshold" => threshold = Some(percentage(&mut arguments, "--threshold")?),
+                "--bench" => bench = Some(positive(&mut arguments, "--bench")?),
+                "--jobs" => jobs = Some(positive(&mut arguments, "--jobs")?),
+                "--timeout" => timeout = Some(seconds(&mut arguments, "--timeout")?),
+                "--watch" => watch = true,
+                "--size" => size = Some(positive(&mut arguments, "--size")?),
+                "--seed" => seed = Some(value(&mut arguments, "--seed")?.parse().map_err(
+                    |_| String::from("Option '--seed' requires a non-negative number")
+                )?),
+                "--planted" => planted = Some(PathBuf::from(value(&mut arguments, "--planted")?)),
+                "-v" => verbosity += 1,
+                "-vv" => verbosity += 2,
+                "--alloc-stats" => alloc_stats = true,
+                flag if flag.starts_with('-') => return Err(format!("Unknown option '{}'", flag)),
+                _ => positional.push(argument),
+            }
+        }
+        let year = match (year, defaults.positive("year")?) {
+            (Some(year), _) | (None, Some(year)) => year as u32,
+            (None, None) => default_year,
+        };
+        let data = data.or(defaults.string("data")?.map(PathBuf::from)).unwrap_or_else(
+            || PathBuf::from("data")
+        ).join(year.to_string());
+        let input = input.unwrap_or_else(|| InputSource::Default(data.clone()));
+        let answers = answers.or(defaults.string("answers")?.map(PathBuf::from)).unwrap_or_else(
+            || data.join("answers.txt")
+        );
+        let history = history.or(defaults.string("history")?.map(PathBuf::from)).unwrap_or_else(
+            || data.join("history.txt")
+        );
+        let output = output.or(defaults.string("output")?.map(PathBuf::from)).unwrap_or_else(
+            || PathBuf::from("report.md")
+        );
+        let format = match (format, defaults.string("format")?) {
+            (Some(format), _) => format,
+            (None, Some(format)) => format.parse()?,
+            (None, None) => Format::Text,
+        };
+        let jobs = jobs.or(defaults.positive("jobs")?);
+        let timeout = match (timeout, defaults.float("timeout")?) {
+            (Some(timeout), _) => Some(timeout),
+            (None, Some(seconds)) => match Duration::try_from_secs_f64(seconds) {
+                Ok(duration) if !duration.is_zero() => Some(duration),
+                _ => return Err(String::from("Key 'timeout' in [runner] must be a positive number of seconds")),
+            },
+            (None, None) => None,
+        };
+        let threshold = match (threshold, defaults.float("threshold")?) {
+            (Some(threshold), _) => threshold,
+            (None, Some(threshold)) if threshold >= 0.0 && threshold.is_finite() => threshold,
+            (None, Some(_)) => return Err(String::from("Key 'threshold' in [runner] must be a non-negative number")),
+            (None, None) => 10.0,
+        };
+        let default_day = match defaults.get("day") {
+            Some(Value::Integer(day)) => day.to_string(),
+            _ => defaults.string("day")?.unwrap_or_else(|| String::from("latest")),
+        };
+        let (session, base_url) = (defaults.string("session")?, defaults.string("base_url")?);
+        let mut positional = positional.into_iter().peekable();
+        let command = match positional.peek().map(String::as_str) {
+            Some("verify") => {
+                positional.next();
+                Command::Verify
+            },
+            Some("compare") => {
+                positional.next();
+                Command::Compare
+            },
+            Some("report") => {
+                positional.next();
+                Command::Report
+            },
+            Some("new-day") | Some("fetch") | Some("repl") | Some("generate") => {
+                let word = positional.next().unwrap();
+                let day = match positional.next().map(|day| day.parse()) {
+                    Some(Ok(day)) => day,
+                    _ => return Err(format!("Command '{}' requires a day number", word)),
+                };
+                match word.as_str() {
+                    "fetch" => Command::Fetch(day),
+                    "repl" => Command::Repl(day),
+                    "generate" => Command::Generate(day),
+                    _ => Command::NewDay(day),
+                }
+            },
+            Some("submit") => {
+                positional.next();
+                match (positional.next().map(|day| day.parse()), positional.next().map(|part| part.parse())) {
+                    (Some(Ok(day)), Some(Ok(part))) => Command::Submit(day, part),
+                    _ => return Err(String::from("Command 'submit' requires a day and a part number")),
+                }
+            },
+            _ => Command::Run,
+        };
+        let selection = match command {
+            Command::NewDay(day) | Command::Fetch(day) | Command::Repl(day) | Command::Generate(day) => {
+                day.to_string().parse()?
+            },
+            Command::Submit(day, part) => format!("{}:{}", day, part.number()).parse()?,
+            _ => positional.next().as_deref().unwrap_or(&default_day).parse()?,
+        };
+        if let Some(argument) = positional.next() {
+            return Err(format!("Unexpected argument '{}'", argument))
+        }
+        Ok(Options {
+            command, selection, year, data, input, inputs, bench, jobs, timeout, watch, alloc_stats, format, answers,
+            history, output, threshold, session, base_url, size, seed, planted, log_level: Level::from_count(verbosity),
+            help,
+        })
+    }
+
+    /// Number of threads to run days on
+    pub fn workers(&self) -> usize {
+        // allocation counters are shared, so days must not allocate at the same time
+        if self.alloc_stats {
+            return 1
+        }
+        // benchmarks run sequentially by default, so that days do not compete for cores
+        self.jobs.unwrap_or_else(|| if self.bench.is_some() {1} else {pool::default_workers()})
+    }
+}
+
+/// Why a command did not succeed, as the exit code and a message to show, if any
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub code: i32,
+    pub message: Option<String>,
+}
+
+impl Failure {
+    /// Invalid use of the command line, such as conflicting options
+    pub fn usage<M: Into<String>>(message: M) -> Self {
+        Failure {code: 2, message: Some(message.into())}
+    }
+
+    /// Error while running a command, such as a file that cannot be read
+    pub fn failed<M: Into<String>>(message: M) -> Self {
+        Failure {code: 1, message: Some(message.into())}
+    }
+
+    /// Failure that was already shown, such as failing days or wrong answers
+    pub fn reported() -> Self {
+        Failure {code: 1, message: None}
+    }
+}
+
+/// How to show the results of running days
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format '{}', expected 'text' or 'json'", s)),
+        }
+    }
+}
+
+/// Path of the configuration file given by `--config` in the command line `arguments`, if any
+pub fn config_path(arguments: &[String]) -> Result<Option<PathBuf>, String> {
+    match arguments.iter().position(|argument| argument == "--config") {
+        Some(index) => arguments.get(index + 1).map(|path| Some(PathBuf::from(path))).ok_or_else(
+            || String::from("Missing value for '--config'")
+        ),
+        None => Ok(None),
+    }
+}
+
+/// Take the value of `option` from the remaining `arguments`
+fn value<I: Iterator<Item=String>>(arguments: &mut I, option: &str) -> Result<String, String> {
+    arguments.next().ok_or_else(|| format!("Missing value for '{}'", option))
+}
+
+/// Take the positive number value of `option` from the remaining `arguments`
+fn positive<I: Iterator<Item=String>>(arguments: &mut I, option: &str) -> Result<usize, String> {
+    match value(arguments, option)?.parse() {
+        Ok(number) if number > 0 => Ok(number),
+        _ => Err(format!("Option '{}' requires a positive number", option)),
+    }
+}
+
+/// Take the non-negative percentage value of `option` from the remaining `arguments`
+fn percentage<I: Iterator<Item=String>>(arguments: &mut I, option: &str) -> Result<f64, String> {
+    match value(arguments, option)?.parse::<f64>() {
+        Ok(number) if number >= 0.0 && number.is_finite() => Ok(number),
+        _ => Err(format!("Option '{}' requires a non-negative percentage", option)),
+    }
+}
+
+/// Take the positive number of seconds value of `option` from the remaining `arguments`
+fn seconds<I: Iterator<Item=String>>(arguments: &mut I, option: &str) -> Result<Duration, String> {
+    // durations cannot be negative, infinite or too large, which `try_from_secs_f64` rejects
+    match value(arguments, option)?.parse::<f64>().map(Duration::try_from_secs_f64) {
+        Ok(Ok(duration)) if !duration.is_zero() => Ok(duration),
+        _ => Err(format!("Option '{}' requires a positive number of seconds", option)),
+    }
+}
@@ -0,0 +1,231 @@
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::cli::{Failure, Options};
+use crate::runner::InputSource;
+
+
+/// Environment variable with the session token
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+/// Environment variable with the base URL of the server
+pub const BASE_URL_VARIABLE: &str = "AOC_BASE_URL";
+
+/// Identification sent with every request, so that the server operators know whom to contact
+const USER_AGENT: &str = concat!("aoc2020_rust/", env!("CARGO_PKG_VERSION"), " (", env!("CARGO_PKG_AUTHORS"), ")");
+/// Longest time to wait for the server at any point of a request
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Minimal HTTP client for the puzzle server, using plain HTTP only
+///
+/// There is no TLS support in std, so reaching the real server requires a
+/// local proxy or stand-in server at the base URL.
+#[derive(Debug, Clone)]
+pub struct Client {
+    host: String,
+    port: u16,
+    /// Path of the base URL, without trailing slash
+    prefix: String,
+    session: String,
+}
+
+impl Client {
+    /// Create a client for the server at `base_url` such as `http://localhost:8080/aoc`
+    pub fn new(base_url: &str, session: &str) -> Result<Self, Error> {
+        let invalid = |reason: &str| Error::new(ErrorKind::InvalidInput, format!("{} '{}'", reason, base_url));
+        let rest = base_url.strip_prefix("http://").ok_or_else(|| invalid("Expected an http:// base URL, got"))?;
+        let (authority, prefix) = match rest.find('/') {
+            Some(index) => (&rest[..index], rest[index..].trim_end_matches('/')),
+            None => (rest, ""),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| invalid("Invalid port in base URL"))?),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(invalid("Missing host in base URL"))
+        }
+        Ok(Client {host: String::from(host), port, prefix: String::from(prefix), session: String::from(session)})
+    }
+
+    /// Create a client from the `AOC_SESSION` and `AOC_BASE_URL` environment variables
+    ///
+    /// The configured `session` and `base_url` are used if the environment variables are not set.
+    /// There is no default server: the real one only speaks HTTPS, and the session token would
+    /// be sent in cleartext before it could redirect.
+    pub fn from_env(session: Option<&str>, base_url: Option<&str>) -> Result<Self, Error> {
+        let session = env::var(SESSION_VARIABLE).ok().or_else(|| session.map(String::from)).ok_or_else(
+            || Error::new(ErrorKind::NotFound, format!(
+                "Set {} or 'session' in the configuration to the session token of your login", SESSION_VARIABLE
+            ))
+        )?;
+        let base_url = env::var(BASE_URL_VARIABLE).ok().or_else(|| base_url.map(String::from)).ok_or_else(
+            || Error::new(ErrorKind::NotFound, format!(
+                "Set {} or 'base_url' in the configuration to a local proxy or stand-in server", BASE_URL_VARIABLE
+            ))
+        )?;
+        Client::new(&base_url, &session)
+    }
+
+    /// Send a `method` request for the `path` below the base URL, returning the body of a successful response
+    ///
+    /// A `form` body is sent URL-encoded. Responses other than `200 OK` are errors.
+    pub fn request(&self, method: &str, path: &str, form: Option<&str>) -> Result<Vec<u8>, Error> {
+        let mut stream = TcpStream::connect((self.host.as_str(), self.port))?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        // HTTP/1.0 responses are never chunked and end when the connection closes
+        let host = if self.port == 80 {self.host.clone()} else {format!("{}:{}", self.host, self.port)};
+        let mut request = format!(
+            "{} {}{} HTTP/1.0\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: session={}\r\n",
+            method, self.prefix, path, host, USER_AGENT, self.session
+        );
+        if let Some(form) = form {
+            request += &format!(
+                "Content-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n", form.len()
+            );
+        }
+        request += "\r\n";
+        request += form.unwrap_or("");
+        stream.write_all(request.as_bytes())?;
+        let mut response = vec![];
+        stream.read_to_end(&mut response)?;
+        let invalid = |reason: String| Error::new(ErrorKind::InvalidData, reason);
+        let header_end = response.windows(4).position(|window| window == b"\r\n\r\n").ok_or_else(
+            || invalid(String::from("Incomplete response from server"))
+        )?;
+        let head = String::from_utf8_lossy(&response[..header_end]);
+        let status = head.lines().next().unwrap_or("");
+        match status.split_whitespace().nth(1) {
+            Some("200") => Ok(response[header_end + 4..].to_vec()),
+            _ => Err(invalid(format!("Server responded with '{}' for {}{}", status, self.prefix, path))),
+        }
+    }
+}
+
+/// Whether a fetch downloaded the input or found it already cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    /// Downloaded a number of bytes
+    Downloaded(usize),
+}
+
+/// Download the input of `day` of `year` to `path`, unless it already has content
+///
+/// Inputs never change, so an input is never downloaded twice. Empty files,
+/// such as those created for a new day, do not count as downloaded.
+pub fn fetch_input(client: &Client, year: u32, day: u32, path: &Path) -> Result<Fetched, Error> {
+    if fs::metadata(path).map(|metadata| metadata.len() > 0).unwrap_or(false) {
+        return Ok(Fetched::Cached)
+    }
+    let input = client.request("GET", &format!("/{}/day/{}/input", year, day), None)?;
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    fs::write(path, &input)?;
+    Ok(Fetched::Downloaded(input.len()))
+}
+
+/// Client for the puzzle server as configured by the `options`
+pub fn configured(options: &Options) -> Result<Client, Error> {
+    Client::from_env(options.session.as_deref(), options.base_url.as_deref())
+}
+
+/// Fetch the input of `day` of the year of the `options` to its default path in the data directory
+pub fn run(options: &Options, day: u32) -> Result<(), Failure> {
+    let path = InputSource::default_path(&options.data, day);
+    match configured(options).and_then(|client| fetch_input(&client, options.year, day, &path)) {
+        Ok(Fetched::Cached) => println!("Input of day {} is already in {}", day, path.display()),
+        Ok(Fetched::Downloaded(bytes)) => println!(
+            "Downloaded input of day {} to {} ({} bytes)", day, path.display(), bytes
+        ),
+        Err(error) => return Err(Failure::failed(format!("Cannot fetch input of day {}: {}", day, error))),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::env;
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    use super::*;
+
+    /// Serve the canned `response` to a single request on a local port
+    ///
+    /// Returns the base URL of the server and a handle to the request it received.
+    pub(crate) fn serve_once(response: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/aoc", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let (mut request, mut buffer) = (vec![], [0; 1024]);
+            // read the head, and then as much of the body as announced
+            loop {
+                let read = stream.read(&mut buffer).unwrap();
+                if read == 0 {
+                    break
+                }
+                request.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&request).into_owned();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length = text.lines().find_map(|line| line.strip_prefix("Content-Length: ")).map_or(
+                        0, |length| length.parse().unwrap()
+                    );
+                    if request.len() >= end + 4 + length {
+                        break
+                    }
+                }
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (base_url, server)
+    }
+
+    #[test]
+    fn base_urls() {
+        let client = Client::new("http://localhost:8080/aoc/", "secret").unwrap();
+        assert_eq!((client.host.as_str(), client.port, client.prefix.as_str()), ("localhost", 8080, "/aoc"));
+        let client = Client::new("http://127.0.0.1", "secret").unwrap();
+        assert_eq!((client.host.as_str(), client.port, client.prefix.as_str()), ("127.0.0.1", 80, ""));
+    }
+
+    #[test]
+    fn invalid_base_urls() {
+        let error = |base_url| Client::new(base_url, "secret").unwrap_err().to_string();
+        assert_eq!(error("https://adventofcode.com"), "Expected an http:// base URL, got 'https://adventofcode.com'");
+        assert_eq!(error("http://localhost:http/"), "Invalid port in base URL 'http://localhost:http/'");
+        assert_eq!(error("http://:8080"), "Missing host in base URL 'http://:8080'");
+    }
+
+    #[test]
+    fn fetches_once() {
+        let (base_url, server) = serve_once("HTTP/1.0 200 OK\r\nContent-Type: text/plain\r\n\r\n1721\n979\n");
+        let client = Client::new(&base_url, "secret").unwrap();
+        let path = env::temp_dir().join(format!("aoc2020_rust_fetch_{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+        assert_eq!(fetch_input(&client, 2020, 1, &path).unwrap(), Fetched::Downloaded(9));
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /aoc/2020/day/1/input HTTP/1.0\r\n"), "{}", request);
+        assert!(request.contains("\r\nCookie: session=secret\r\n"), "{}", request);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1721\n979\n");
+        // the server is gone, so this must not connect
+        assert_eq!(fetch_input(&client, 2020, 1, &path).unwrap(), Fetched::Cached);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn rejects_failed_requests() {
+        let (base_url, server) = serve_once("HTTP/1.0 404 Not Found\r\n\r\nPlease don't request this endpoint");
+        let client = Client::new(&base_url, "secret").unwrap();
+        let error = client.request("GET", "/2020/day/26/input", None).unwrap_err();
+        assert_eq!(error.to_string(), "Server responded with 'HTTP/1.0 404 Not Found' for /aoc/2020/day/26/input");
+        server.join().unwrap();
+    }
+}
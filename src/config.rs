@@ -0,0 +1,341 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use crate::error::AocError;
+
+
+/// File read for configuration unless another is given
+pub const DEFAULT_PATH: &str = "aoc.toml";
+
+/// Value of a configuration key
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(value) => write!(f, "{:?}", value),
+            Value::Integer(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{}", value),
+            Value::Boolean(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Read a literal such as `"shiny gold"`, `25`, `0.5` or `true`
+fn parse_value(literal: &str) -> Option<Value> {
+    if let Some(quoted) = literal.strip_prefix('"') {
+        let quoted = quoted.strip_suffix('"')?;
+        let mut value = String::new();
+        let mut chars = quoted.chars();
+        while let Some(c) = chars.next() {
+            value.push(match c {
+                '\\' => match chars.next()? {
+                    'n' => '\n',
+                    't' => '\t',
+                    escaped @ ('"' | '\\') => escaped,
+                    _ => return None,
+                },
+                '"' => return None,
+                c => c,
+            });
+        }
+        return Some(Value::String(value))
+    }
+    match literal {
+        "true" => Some(Value::Boolean(true)),
+        "false" => Some(Value::Boolean(false)),
+        _ => literal.replace('_', "").parse().map(Value::Integer).or_else(
+            |_| literal.parse().map(Value::Float)
+        ).ok(),
+    }
+}
+
+/// Keys and values of one `[name]` table of a configuration
+#[derive(Debug, Clone, Default)]
+pub struct Section {
+    name: String,
+    values: BTreeMap<String, Value>,
+}
+
+impl Section {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.values.get(key)
+    }
+
+    /// Fail if there are any keys besides the `known` ones, which are likely typos
+    pub fn expect_keys(&self, known: &[&str]) -> Result<(), String> {
+        match self.values.keys().find(|key| !known.contains(&key.as_str())) {
+            Some(key) if known.is_empty() => Err(format!("Unknown key '{}' in [{}], which takes no keys", key, self.name)),
+            Some(key) => Err(format!(
+                "Unknown key '{}' in [{}], expected one of {}", key, self.name, known.join(", ")
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn mismatch(&self, key: &str, expected: &str) -> String {
+        format!("Key '{}' in [{}] must be {}, got {}", key, self.name, expected, self.values[key])
+    }
+
+    pub fn string(&self, key: &str) -> Result<Option<String>, String> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Value::String(value)) => Ok(Some(value.clone())),
+            Some(_) => Err(self.mismatch(key, "a string")),
+        }
+    }
+
+    pub fn integer(&self, key: &str) -> Result<Option<i64>, String> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Value::Integer(value)) => Ok(Some(*value)),
+            Some(_) => Err(self.mismatch(key, "an integer")),
+        }
+    }
+
+    /// Positive integer value of `key`, such as a count
+    pub fn positive(&self, key: &str) -> Result<Option<usize>, String> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Value::Integer(value)) if *value > 0 => Ok(Some(*value as usize)),
+            Some(_) => Err(self.mismatch(key, "a positive integer")),
+        }
+    }
+
+    /// Number value of `key`, which may also be written as an integer
+    pub fn float(&self, key: &str) -> Result<Option<f64>, String> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Value::Float(value)) => Ok(Some(*value)),
+            Some(Value::Integer(value)) => Ok(Some(*value as f64)),
+            Some(_) => Err(self.mismatch(key, "a number")),
+        }
+    }
+
+    pub fn boolean(&self, key: &str) -> Result<Option<bool>, String> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Value::Boolean(value)) => Ok(Some(*value)),
+            Some(_) => Err(self.mismatch(key, "true or false")),
+        }
+    }
+}
+
+/// Configuration of the runner and per-day parameters, read from a TOML-like file
+///
+/// Only a subset of TOML is supported: `[name]` tables, which may be dotted, with `key = value` pairs of
+/// strings, integers, floats and booleans, and `#` comments. For example:
+///
+/// ```text
+/// [runner]
+/// day = "all"
+/// timeout = 10
+///
+/// [y2020.day9]
+/// preamble = 25
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    sections: BTreeMap<String, Section>,
+}
+
+impl Config {
+    /// Read the configuration from the `text` of a file
+    pub fn parse(text: &str) -> Result<Self, AocError> {
+        let mut sections = BTreeMap::new();
+        let mut current = String::new();
+        for (index, raw) in text.lines().enumerate() {
+            let located = |column: usize, reason: &str| AocError::parse(raw, column, reason).at_line(index + 1, raw);
+            let line = strip_comment(raw).trim();
+            let column = raw.len() - raw.trim_start().len() + 1;
+            if line.is_empty() {
+                continue
+            }
+            if let Some(name) = line.strip_prefix('[') {
+                let name = name.strip_suffix(']').map(str::trim).filter(
+                    |name| name.split('.').all(is_bare)
+                ).ok_or_else(|| located(column, "expected a table name such as '[runner]'"))?;
+                if sections.contains_key(name) {
+                    return Err(located(column, "duplicate table"))
+                }
+                current = String::from(name);
+                sections.insert(current.clone(), Section {name: current.clone(), values: BTreeMap::new()});
+                continue
+            }
+            let (key, literal) = line.split_once('=').ok_or_else(|| located(column, "expected 'key = value'"))?;
+            let (key, literal) = (key.trim(), literal.trim());
+            let section = sections.get_mut(&current).ok_or_else(
+                || located(column, "keys must be inside a table such as '[runner]'")
+            )?;
+            if !is_bare(key) {
+                return Err(located(column, "expected a key of letters, digits, '_' and '-'"))
+            }
+            let value = parse_value(literal).ok_or_else(
+                || located(raw.find(literal).map_or(column, |at| at + 1), "expected a string, number or boolean")
+            )?;
+            if section.values.insert(String::from(key), value).is_some() {
+                return Err(located(column, "duplicate key"))
+            }
+        }
+        Ok(Config {sections})
+    }
+
+    /// Read the configuration at `path`, which may only be missing if it is `optional`
+    pub fn load(path: &Path, optional: bool) -> Result<Self, AocError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Config::parse(&text).map_err(|error| error.in_file(&path.display().to_string())),
+            Err(error) if optional && error.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(error) => Err(AocError::Io(error)),
+        }
+    }
+
+    /// The table `name`, which is empty if it is not configured
+    pub fn section(&self, name: &str) -> Section {
+        self.sections.get(name).cloned().unwrap_or_else(
+            || Section {name: String::from(name), values: BTreeMap::new()}
+        )
+    }
+
+    /// Names of all tables, in order
+    pub fn names(&self) -> impl Iterator<Item=&str> + '_ {
+        self.sections.keys().map(String::as_str)
+    }
+}
+
+/// Whether `name` is a key or part of a table name, made of letters, digits, `_` and `-`
+fn is_bare(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Year and day of a table `name` such as `y2020.day9`, which sets the parameters of that day
+pub fn day_table(name: &str) -> Option<(u32, u32)> {
+    let (year, day) = name.strip_prefix('y')?.split_once(".day")?;
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+/// Remove a `#` comment from a `line`, except inside strings
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..index],
+            _ => (),
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Line, column and reason of the error parsing `text`
+    fn error_at(text: &str) -> (usize, usize, String) {
+        match Config::parse(text) {
+            Err(AocError::Parse {line: Some(line), column, reason, ..}) => (line, column, reason),
+            other => panic!("expected a located parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(parse_value("\"shiny gold\""), Some(Value::String(String::from("shiny gold"))));
+        assert_eq!(
+            parse_value(r#""a \"quoted\" \\ tab\t""#), Some(Value::String(String::from("a \"quoted\" \\ tab\t")))
+        );
+        assert_eq!(parse_value("25"), Some(Value::Integer(25)));
+        assert_eq!(parse_value("-1_000"), Some(Value::Integer(-1000)));
+        assert_eq!(parse_value("0.5"), Some(Value::Float(0.5)));
+        assert_eq!(parse_value("true"), Some(Value::Boolean(true)));
+        assert_eq!(parse_value("false"), Some(Value::Boolean(false)));
+    }
+
+    #[test]
+    fn invalid_values() {
+        assert_eq!(parse_value("\"unterminated"), None);
+        assert_eq!(parse_value(r#""bad \q escape""#), None);
+        assert_eq!(parse_value(r#""two" "strings""#), None);
+        assert_eq!(parse_value("yes"), None);
+        assert_eq!(parse_value(""), None);
+    }
+
+    #[test]
+    fn comments() {
+        assert_eq!(strip_comment("day = 7 # the latest"), "day = 7 ");
+        assert_eq!(strip_comment("# only a comment"), "");
+        assert_eq!(strip_comment(r##"root = "#1 gold" # colour"##), r##"root = "#1 gold" "##);
+        assert_eq!(strip_comment(r##"root = "a \"#\" b""##), r##"root = "a \"#\" b""##);
+        assert_eq!(strip_comment("no comment"), "no comment");
+    }
+
+    #[test]
+    fn tables_and_keys() {
+        let config = Config::parse("\
+# defaults
+[runner]
+day = \"all\"  # every day
+timeout = 10
+
+[y2020.day9]
+preamble = 5
+").unwrap();
+        assert_eq!(config.names().collect::<Vec<_>>(), vec!["runner", "y2020.day9"]);
+        let runner = config.section("runner");
+        assert_eq!(runner.string("day"), Ok(Some(String::from("all"))));
+        assert_eq!(runner.float("timeout"), Ok(Some(10.0)));
+        assert_eq!(runner.integer("jobs"), Ok(None));
+        assert_eq!(config.section("y2020.day9").positive("preamble"), Ok(Some(5)));
+        assert!(config.section("y2020.day1").get("target").is_none());
+    }
+
+    #[test]
+    fn mismatched_and_unknown_keys() {
+        let config = Config::parse("[runner]\njobs = \"many\"\nthreads = 4\n").unwrap();
+        let runner = config.section("runner");
+        let mismatch = "Key 'jobs' in [runner] must be a positive integer, got \"many\"";
+        assert_eq!(runner.positive("jobs"), Err(String::from(mismatch)));
+        assert_eq!(
+            runner.expect_keys(&["jobs"]),
+            Err(String::from("Unknown key 'threads' in [runner], expected one of jobs"))
+        );
+    }
+
+    #[test]
+    fn malformed_lines() {
+        assert_eq!(error_at("day = 7"), (1, 1, String::from("keys must be inside a table such as '[runner]'")));
+        assert_eq!(error_at("[runner]\n  day 7"), (2, 3, String::from("expected 'key = value'")));
+        assert_eq!(error_at("[runner"), (1, 1, String::from("expected a table name such as '[runner]'")));
+        assert_eq!(error_at("[y2020..day9]"), (1, 1, String::from("expected a table name such as '[runner]'")));
+        assert_eq!(error_at("[runner]\n[runner]"), (2, 1, String::from("duplicate table")));
+        assert_eq!(error_at("[runner]\nday = 1\nday = 2"), (3, 1, String::from("duplicate key")));
+        assert_eq!(error_at("[runner]\nday = seven"), (2, 7, String::from("expected a string, number or boolean")));
+        let reason = "expected a key of letters, digits, '_' and '-'";
+        assert_eq!(error_at("[runner]\nday of month = 7"), (2, 1, String::from(reason)));
+    }
+
+    #[test]
+    fn day_tables() {
+        assert_eq!(day_table("y2020.day9"), Some((2020, 9)));
+        assert_eq!(day_table("y2021.day25"), Some((2021, 25)));
+        assert_eq!(day_table("day9"), None);
+        assert_eq!(day_table("runner"), None);
+        assert_eq!(day_table("y2020.dayX"), None);
+    }
+}
@@ -0,0 +1,107 @@
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+use std::time::Duration;
+
+use crate::runner::format_duration;
+
+/// Anything that can go wrong while reading, parsing or solving a puzzle
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    /// Malformed input, with as much location information as is known
+    Parse {
+        file: Option<String>,
+        /// Line of the input, counting from 1
+        line: Option<usize>,
+        /// Character of the offending `text`, counting from 1
+        column: usize,
+        text: String,
+        reason: String,
+    },
+    /// Well-formed input which does not lead to a solution
+    InvalidState(String),
+    /// Solving took longer than allowed
+    TimedOut(Duration),
+    /// Solving panicked, at the source `location` if it is known
+    Panicked {message: String, location: Option<String>},
+}
+
+impl AocError {
+    /// Malformed `text`, with the offending part starting at `column`
+    pub fn parse<R: Into<String>>(text: &str, column: usize, reason: R) -> Self {
+        AocError::Parse {file: None, line: None, column, text: String::from(text), reason: reason.into()}
+    }
+
+    pub fn invalid<R: Into<String>>(reason: R) -> Self {
+        AocError::InvalidState(reason.into())
+    }
+
+    /// Locate a parse error at the `line` with content `text`, unless already located
+    pub fn at_line(self, line: usize, text: &str) -> Self {
+        match self {
+            AocError::Parse {file, line: None, column, text: old_text, reason} => AocError::Parse {
+                file, line: Some(line), column,
+                text: if old_text.is_empty() {String::from(text)} else {old_text}, reason,
+            },
+            other => other,
+        }
+    }
+
+    /// Locate a parse error in the input `file`, unless already located
+    pub fn in_file(self, file: &str) -> Self {
+        match self {
+            AocError::Parse {file: None, line, column, text, reason} => AocError::Parse {
+                file: Some(String::from(file)), line, column, text, reason,
+            },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io(error) => write!(f, "I/O error: {}", error),
+            AocError::Parse {file, line, column, text, reason} => {
+                let line = line.map(|line| line.to_string()).unwrap_or_else(|| String::from("?"));
+                write!(f, "{}:{}:{}: {}", file.as_deref().unwrap_or("<input>"), line, column, reason)?;
+                write!(f, "\n    {}\n    {:>width$}", text, "^", width = *column.max(&1))
+            },
+            AocError::InvalidState(reason) => write!(f, "invalid puzzle state: {}", reason),
+            AocError::TimedOut(timeout) => write!(f, "timed out after {}", format_duration(*timeout)),
+            AocError::Panicked {message, location: Some(location)} => write!(f, "panicked at {}: {}", location, message),
+            AocError::Panicked {message, location: None} => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(error: io::Error) -> Self {
+        AocError::Io(error)
+    }
+}
+
+impl From<ParseIntError> for AocError {
+    /// Malformed number; its text and location must be added with `at_line`
+    fn from(error: ParseIntError) -> Self {
+        AocError::parse("", 1, format!("invalid number ({})", error))
+    }
+}
+
+impl From<Infallible> for AocError {
+    fn from(error: Infallible) -> Self {
+        match error {}
+    }
+}
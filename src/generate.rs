@@ -0,0 +1,110 @@
+use std::fmt::Display;
+use std::fs;
+use std::io::Error;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::cli::{Failure, Options};
+use crate::solution::{Part, Registry};
+
+
+/// Seeded pseudo-random number generator, so that generated inputs can be reproduced
+///
+/// This is SplitMix64, which is fast and random enough for puzzle inputs, but not for anything secret.
+#[derive(Debug, Clone)]
+pub struct Random(u64);
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Random(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Number from `low` to `high`, inclusive
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low <= high, "empty range {}..={}", low, high);
+        match (high - low).checked_add(1) {
+            // scale instead of taking the remainder, which is biased towards low numbers
+            Some(span) => low + ((self.next_u64() as u128 * span as u128) >> 64) as u64,
+            None => self.next_u64(),
+        }
+    }
+
+    /// Index below `bound`, such as of a slice of that length
+    pub fn below(&mut self, bound: usize) -> usize {
+        self.between(0, bound as u64 - 1) as usize
+    }
+
+    /// Whether an event with a `percent` chance happens
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.between(1, 100) <= percent
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Put the `items` in random order
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.below(index + 1));
+        }
+    }
+}
+
+/// Seed for a generator if none is given, which differs between runs
+pub fn seed() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_nanos() as u64).unwrap_or(0)
+}
+
+/// Input generated for a day, with the answers known from how it was generated
+#[derive(Debug, Clone, Default)]
+pub struct Generated {
+    pub input: String,
+    pub answers: Vec<(Part, String)>,
+}
+
+impl Generated {
+    pub fn new(input: String) -> Self {
+        Generated {input, answers: vec![]}
+    }
+
+    /// Add the known `answer` of `part`
+    pub fn plant<A: Display>(mut self, part: Part, answer: A) -> Self {
+        self.answers.push((part, answer.to_string()));
+        self
+    }
+}
+
+/// Write the planted answers of `generated` input for `day` to `path`, in the format of expected answers
+pub fn write_answers(path: &Path, day: u32, generated: &Generated) -> Result<(), Error> {
+    let lines: String = generated.answers.iter().map(
+        |(part, answer)| format!("{}:{} {}\n", day, part.number(), answer)
+    ).collect();
+    fs::write(path, lines)
+}
+
+/// Write a random input of `day` to stdout, and its known answers to the planted path of the `options`, if any
+pub fn run(options: &Options, registry: &Registry, day: u32) -> Result<(), Failure> {
+    let seed = options.seed.unwrap_or_else(seed);
+    let mut random = Random::new(seed);
+    let generated = registry.get(options.year, day).unwrap().generate(&mut random, options.size).map_err(
+        |error| Failure::failed(format!("Cannot generate an input of day {}: {}", day, error))
+    )?;
+    print!("{}", generated.input);
+    eprintln!("Generated an input of day {} with seed {}", day, seed);
+    if let Some(path) = &options.planted {
+        write_answers(path, day, &generated).map_err(
+            |error| Failure::failed(format!("Cannot write answers to {}: {}", path.display(), error))
+        )?;
+        eprintln!("Wrote {} known answer(s) to {}", generated.answers.len(), path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,209 @@
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{Error, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::cli::{Failure, Options};
+use crate::runner::{format_duration, DayResult};
+use crate::solution::Part;
+use crate::utility::load_records;
+
+
+/// Median time of one part in one benchmark of a build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Identifier of the executable that was benchmarked
+    pub build: String,
+    pub day: u32,
+    pub part: Part,
+    pub median: Duration,
+}
+
+impl Record {
+    /// Read a literal such as `5f1d3a9c0b7e4d21 7:2 183412`, with the median in nanoseconds
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        let build = String::from(fields.next()?);
+        let (day, part) = fields.next()?.split_once(':')?;
+        let median = Duration::from_nanos(fields.next()?.parse().ok()?);
+        match fields.next() {
+            None => Some(Record {build, day: day.parse().ok()?, part: part.parse().ok()?, median}),
+            Some(_) => None,
+        }
+    }
+}
+
+/// Identifier of the running executable, which changes whenever it is rebuilt differently
+///
+/// This is a FNV-1a hash of the executable, so it does not depend on version control.
+pub fn build_id() -> Result<String, Error> {
+    let executable = fs::read(env::current_exe()?)?;
+    let hash = executable.iter().fold(
+        0xcbf2_9ce4_8422_2325_u64, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    );
+    Ok(format!("{:016x}", hash))
+}
+
+/// Records of the median part timings of a benchmark `result` of a `build`
+pub fn records(build: &str, result: &DayResult) -> Vec<Record> {
+    result.parts.iter().map(|part| Record {
+        build: String::from(build), day: result.day, part: part.part, median: part.elapsed,
+    }).collect()
+}
+
+/// Append `records` to the history file at `path`, creating it if needed
+pub fn append(path: &Path, records: &[Record]) -> Result<(), Error> {
+    let mut file = OpenOptions::new().append(true).create(true).open(path)?;
+    for record in records {
+        writeln!(file, "{} {}:{} {}", record.build, record.day, record.part.number(), record.median.as_nanos())?;
+    }
+    Ok(())
+}
+
+/// Read all records from the history file at `path`, oldest first
+///
+/// Empty lines and lines starting with `#` are ignored.
+pub fn load(path: &Path) -> Result<Vec<Record>, Error> {
+    load_records(path, |line| Record::parse(line).ok_or_else(
+        || format!("expected a record such as '5f1d3a9c0b7e4d21 7:2 183412', got '{}'", line)
+    ))
+}
+
+/// Latest timing of a part compared to the best timing before it
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub day: u32,
+    pub part: Part,
+    pub latest: Duration,
+    /// Fastest of all earlier timings, if there are any
+    pub best: Option<Duration>,
+}
+
+impl Comparison {
+    /// Relative change of the latest timing to the best one, in percent
+    pub fn change(&self) -> Option<f64> {
+        self.best.map(|best| (self.latest.as_secs_f64() / best.as_secs_f64().max(1e-9) - 1.0) * 100.0)
+    }
+
+    /// Whether the latest timing is more than `threshold` percent slower than the best one
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+}
+
+/// Compare the latest timing of each `selected` day and part in the `records` to its earlier best
+///
+/// Parts without any records are skipped.
+pub fn compare(records: &[Record], selected: &[(u32, Vec<Part>)]) -> Vec<Comparison> {
+    selected.iter().flat_map(|(day, parts)| parts.iter().map(move |part| (*day, *part))).filter_map(
+        |(day, part)| {
+            let mut timings = records.iter().filter(
+                |record| record.day == day && record.part == part
+            ).map(|record| record.median).collect::<Vec<_>>();
+            let latest = timings.pop()?;
+            Some(Comparison {day, part, latest, best: timings.into_iter().min()})
+        }
+    ).collect()
+}
+
+/// Print each comparison, flagging those more than `threshold` percent slower than their best
+///
+/// Returns whether no part regressed.
+pub fn print_comparison(comparisons: &[Comparison], threshold: f64) -> bool {
+    let mut regressed = 0;
+    for comparison in comparisons {
+        print!(
+            "Day {:>2} Part {}: latest {:>10}", comparison.day, comparison.part.number(),
+            format_duration(comparison.latest)
+        );
+        match (comparison.best, comparison.change()) {
+            (Some(best), Some(change)) => {
+                print!(" | best {:>10} | {:+7.1}%", format_duration(best), change);
+                if comparison.regressed(threshold) {
+                    regressed += 1;
+                    print!(" SLOWER");
+                }
+                println!();
+            },
+            _ => println!(" | no earlier timing"),
+        }
+    }
+    println!("{} compared, {} slower by more than {}%", comparisons.len(), regressed, threshold);
+    regressed == 0
+}
+
+/// Compare the latest timings of the `selected` days to their best earlier ones in the history
+///
+/// Fails if any part got slower than the threshold of the `options` allows.
+pub fn run(options: &Options, selected: &[(u32, Vec<Part>)]) -> Result<(), Failure> {
+    let records = load(&options.history).map_err(
+        |error| Failure::failed(format!("Cannot read history from {}: {}", options.history.display(), error))
+    )?;
+    match print_comparison(&compare(&records, selected), options.threshold) {
+        true => Ok(()),
+        false => Err(Failure::reported()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(build: &str, day: u32, part: Part, nanos: u64) -> Record {
+        Record {build: String::from(build), day, part, median: Duration::from_nanos(nanos)}
+    }
+
+    fn comparison(latest: u64, best: Option<u64>) -> Comparison {
+        Comparison {
+            day: 1, part: Part::One, latest: Duration::from_nanos(latest), best: best.map(Duration::from_nanos),
+        }
+    }
+
+    #[test]
+    fn record_lines() {
+        let expected = record("5f1d3a9c0b7e4d21", 7, Part::Two, 183412);
+        assert_eq!(Record::parse("5f1d3a9c0b7e4d21 7:2 183412"), Some(expected));
+        assert_eq!(Record::parse("5f1d3a9c0b7e4d21 7:2 183412 more"), None);
+        assert_eq!(Record::parse("5f1d3a9c0b7e4d21 7:3 183412"), None);
+        assert_eq!(Record::parse("5f1d3a9c0b7e4d21 7 183412"), None);
+        assert_eq!(Record::parse("5f1d3a9c0b7e4d21 7:2 1.5"), None);
+        assert_eq!(Record::parse("5f1d3a9c0b7e4d21"), None);
+    }
+
+    #[test]
+    fn latest_against_earlier_best() {
+        let records = vec![
+            record("a", 1, Part::One, 300), record("a", 1, Part::Two, 900), record("b", 1, Part::One, 200),
+            record("a", 2, Part::One, 50), record("c", 1, Part::One, 100), record("d", 1, Part::One, 250),
+        ];
+        let selected = [(1, vec![Part::One, Part::Two]), (2, vec![Part::One]), (3, vec![Part::One])];
+        let found = compare(&records, &selected).iter().map(|comparison| (
+            comparison.day, comparison.part, comparison.latest.as_nanos(), comparison.best.map(|best| best.as_nanos())
+        )).collect::<Vec<_>>();
+        // the latest timing is not its own best, and parts without records are skipped
+        assert_eq!(found, vec![(1, Part::One, 250, Some(100)), (1, Part::Two, 900, None), (2, Part::One, 50, None)]);
+    }
+
+    #[test]
+    fn regression_threshold() {
+        assert_eq!(comparison(150, Some(100)).change(), Some(50.0));
+        assert!(!comparison(150, Some(100)).regressed(50.0));
+        assert!(comparison(150, Some(100)).regressed(49.9));
+        assert!(!comparison(50, Some(100)).regressed(0.0));
+        assert_eq!(comparison(150, None).change(), None);
+        assert!(!comparison(150, None).regressed(0.0));
+    }
+
+    #[test]
+    fn malformed_history() {
+        let path = env::temp_dir().join(format!("aoc2020_rust_history_{}.txt", std::process::id()));
+        fs::write(&path, "# build day:part median\n\n5f1d3a9c0b7e4d21 7:2 183412\n5f1d3a9c0b7e4d21 7:2\n").unwrap();
+        let error = load(&path).unwrap_err().to_string();
+        let reason = "expected a record such as '5f1d3a9c0b7e4d21 7:2 183412', got '5f1d3a9c0b7e4d21 7:2'";
+        assert!(error.ends_with(&format!(":4: {}", reason)), "{}", error);
+        fs::write(&path, "# build day:part median\n\n5f1d3a9c0b7e4d21 7:2 183412\n").unwrap();
+        assert_eq!(load(&path).unwrap(), vec![record("5f1d3a9c0b7e4d21", 7, Part::Two, 183412)]);
+        fs::remove_file(&path).unwrap();
+    }
+}
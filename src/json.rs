@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use crate::alloc::AllocStats;
+use crate::runner::{DayResult, PartResult};
+
+
+/// Quote and escape `s` as a JSON string
+pub fn string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Format a `duration` as integer nanoseconds
+pub fn nanoseconds(duration: Duration) -> String {
+    duration.as_nanos().to_string()
+}
+
+/// Format allocation `stats` as a field named `name`, if they were counted
+fn alloc_field(name: &str, stats: Option<AllocStats>) -> String {
+    match stats {
+        Some(stats) => format!(
+            ", \"{}\": {{\"allocations\": {}, \"bytes\": {}, \"peak_bytes\": {}}}",
+            name, stats.allocations, stats.bytes, stats.peak
+        ),
+        None => String::new(),
+    }
+}
+
+fn part_result(result: &PartResult) -> String {
+    format!(
+        "{{\"part\": {}, \"answer\": {}, \"elapsed_ns\": {}{}}}",
+        result.part.number(), string(&result.answer), nanoseconds(result.elapsed), alloc_field("alloc", result.alloc)
+    )
+}
+
+fn day_result(result: &DayResult) -> String {
+    let parts: Vec<String> = result.parts.iter().map(part_result).collect();
+    format!(
+        "{{\"day\": {}, \"runs\": {}, \"parse_ns\": {}, \"elapsed_ns\": {}{}{}, \"parts\": [\n    {}\n  ]}}",
+        result.day, result.runs, nanoseconds(result.parse), nanoseconds(result.elapsed()),
+        alloc_field("parse_alloc", result.parse_alloc), alloc_field("alloc", result.alloc), parts.join(",\n    ")
+    )
+}
+
+/// Format the `results` of several days of `year` as a JSON document
+///
+/// The schema is stable; fields may be added but are never removed or renamed:
+///
+/// ```text
+/// {"year": 2020, "results": [
+///   {"day": 13, "runs": 1, "parse_ns": 2260, "elapsed_ns": 13210, "parts": [
+///     {"part": 1, "answer": "3966", "elapsed_ns": 5420},
+///     {"part": 2, "answer": "800177252346225", "elapsed_ns": 5540}
+///   ]}
+/// ]}
+/// ```
+///
+/// Answers are strings. Timings are in nanoseconds, and are the median of all runs if there are several.
+/// With allocation counting, days also have `parse_alloc` and `alloc` fields and parts an `alloc` field,
+/// such as `{"allocations": 12, "bytes": 4096, "peak_bytes": 2048}`.
+pub fn day_results(year: u32, results: &[DayResult]) -> String {
+    let days: Vec<String> = results.iter().map(day_result).collect();
+    format!("{{\"year\": {}, \"results\": [\n  {}\n]}}", year, days.join(",\n  "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_strings() {
+        assert_eq!(string(""), r#""""#);
+        assert_eq!(string("1019571"), r#""1019571""#);
+        assert_eq!(string("shiny gold ✨"), r#""shiny gold ✨""#);
+    }
+
+    #[test]
+    fn escaped_strings() {
+        assert_eq!(string(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(string(r"C:\data"), r#""C:\\data""#);
+        assert_eq!(string("..#\n.#.\r\n\t"), r#""..#\n.#.\r\n\t""#);
+        assert_eq!(string("\u{0}\u{1b}\u{1f} "), r#""\u0000\u001b\u001f ""#);
+    }
+}
@@ -0,0 +1,35 @@
+//! Solutions for Advent of Code, with a runner to solve, time and verify them
+//!
+//! The solutions of each year are in their own module, such as `y2020::day7`.
+pub mod utility;
+pub mod alloc;
+pub mod log;
+pub mod error;
+pub mod config;
+pub mod solution;
+pub mod selection;
+pub mod runner;
+pub mod bench;
+pub mod cli;
+pub mod json;
+pub mod verify;
+pub mod history;
+pub mod report;
+pub mod pool;
+pub mod scaffold;
+pub mod client;
+pub mod submit;
+pub mod watch;
+pub mod repl;
+pub mod generate;
+pub mod y2020;
+
+use solution::Registry;
+
+
+/// All days of all years with a solution
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+    y2020::register(&mut registry);
+    registry
+}
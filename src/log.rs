@@ -0,0 +1,118 @@
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Quiet as u8);
+
+thread_local! {
+    /// Whether the current thread shows no messages whatever the level, such as while repeating runs
+    static MUTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Detail of the messages solvers show about their inner workings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// No messages, the default
+    Quiet,
+    /// Intermediate facts, such as the entries found by day 1
+    Verbose,
+    /// Every step of a computation, such as each generation of seats of day 11
+    Trace,
+}
+
+impl Level {
+    /// The level of `count` verbosity flags, such as 2 for `-vv`
+    pub fn from_count(count: usize) -> Self {
+        match count {
+            0 => Level::Quiet,
+            1 => Level::Verbose,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Quiet => write!(f, "quiet"),
+            Level::Verbose => write!(f, "verbose"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+/// Show messages up to `level` from now on
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Whether messages of `level` are shown
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= LEVEL.load(Ordering::Relaxed) && !MUTED.with(Cell::get)
+}
+
+/// Run `f` without showing any messages on the current thread, whatever the level
+///
+/// Other threads, such as those running other days, still show their messages.
+pub fn quietly<R>(f: impl FnOnce() -> R) -> R {
+    /// Restores whether the thread was muted, even if `f` panics
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            MUTED.with(|muted| muted.set(self.0));
+        }
+    }
+
+    let _restore = Restore(MUTED.with(|muted| muted.replace(true)));
+    f()
+}
+
+/// Show a `message` of `level`, on stderr so that it does not mix with the answers
+///
+/// Use the `verbose!` and `trace!` macros instead, which skip formatting messages that are not shown.
+pub fn write(level: Level, message: fmt::Arguments<'_>) {
+    eprintln!("[{}] {}", level, message);
+}
+
+/// Show a message about an intermediate fact with `-v`, formatted like `format!`
+#[macro_export]
+macro_rules! verbose {
+    ($($argument:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Verbose) {
+            $crate::log::write($crate::log::Level::Verbose, format_args!($($argument)*))
+        }
+    };
+}
+
+/// Show a message about a single step with `-vv`, formatted like `format!`
+#[macro_export]
+macro_rules! trace {
+    ($($argument:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            $crate::log::write($crate::log::Level::Trace, format_args!($($argument)*))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use super::*;
+
+    #[test]
+    fn quietly_mutes_until_done() {
+        // quiet messages are always shown unless muted, whatever the level set by other tests
+        assert!(enabled(Level::Quiet));
+        assert!(!quietly(|| enabled(Level::Quiet)));
+        // nesting keeps the outer call muted
+        assert!(!quietly(|| {
+            quietly(|| ());
+            enabled(Level::Quiet)
+        }));
+        assert!(panic::catch_unwind(|| quietly(|| panic!("solver failed"))).is_err());
+        assert!(enabled(Level::Quiet));
+    }
+}
@@ -1,65 +1,177 @@
 use std::env;
-use std::io::Error;
-use std::time::{Instant, Duration};
+use std::fs;
+use std::path::PathBuf;
+use std::process::exit;
+use std::time::Instant;
 
-mod utility;
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
+use aoc2020_rust::{alloc, log, registry, client, generate, json, history, pool, report, repl, scaffold, submit, watch};
+use aoc2020_rust::runner::{
+    run_solver, run_inputs, with_timeout, catch_panics, print_outcomes, Outcome, print_result, print_summary,
+    format_duration, InputSource,
+};
+use aoc2020_rust::bench::{bench_solver, print_bench};
+use aoc2020_rust::cli::{config_path, Options, Command, Failure, Format, USAGE};
+use aoc2020_rust::config::{self, Config};
+use aoc2020_rust::solution::{Part, Registry};
+use aoc2020_rust::verify::{Answers, print_verification};
 
 
-/// Format a duration nicely
-fn format_duration(delta: Duration) -> String {
-    let mut fractional = delta.as_secs_f64();
-    for symbol in ["s", "ms", "μs", "ns"].iter() {
-        if fractional > 0.5 {
-            return format!("{:.2} {}", fractional, symbol);
+#[global_allocator]
+static ALLOCATOR: alloc::Counting = alloc::Counting;
+
+fn main() {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    let config = match config_path(&arguments) {
+        Ok(path) => {
+            let optional = path.is_none();
+            let path = path.unwrap_or_else(|| PathBuf::from(config::DEFAULT_PATH));
+            match Config::load(&path, optional) {
+                Ok(config) => config,
+                Err(error) => {
+                    eprintln!("Cannot read configuration from {}: {}", path.display(), error);
+                    exit(2)
+                }
+            }
+        },
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            exit(2)
         }
-        else {
-            fractional *= 1000.0;
+    };
+    if let Some(name) = config.names().find(|name| *name != "runner" && config::day_table(name).is_none()) {
+        eprintln!("Invalid configuration: unknown table [{}], expected [runner] or [yYYYY.dayN]", name);
+        exit(2)
+    }
+    let mut registry = registry();
+    let default_year = registry.years().last().copied().unwrap_or(2020);
+    let options = match Options::from_args(arguments, default_year, &config.section("runner")) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            exit(2)
         }
+    };
+    if let Err(message) = registry.configure(&config) {
+        eprintln!("Invalid configuration: {}", message);
+        exit(2)
+    }
+    // solvers which time out keep running on their own threads, which outlive any borrow
+    let registry: &'static _ = Box::leak(Box::new(registry));
+    if options.help {
+        print!("{}", USAGE);
+        return
+    }
+    if options.alloc_stats {
+        alloc::enable();
+    }
+    log::set_level(options.log_level);
+    if let Err(failure) = dispatch(&options, registry) {
+        if let Some(message) = failure.message {
+            eprintln!("{}", message);
+        }
+        exit(failure.code)
     }
-    format!("{:?}", delta)
 }
 
-
-fn run_solver(solver: fn() -> Result<(), Error>) -> Result<(), Error> {
-    let pre = Instant::now();
-    solver()?;
-    let end = Instant::now();
-    println!("[> Elapsed {} <]", format_duration(end-pre));
-    Ok(())
+/// Run the command of the `options` with the solvers of the `registry`
+fn dispatch(options: &Options, registry: &'static Registry) -> Result<(), Failure> {
+    match options.command {
+        Command::NewDay(day) => return scaffold::run(options, day),
+        Command::Fetch(day) => return client::run(options, day),
+        _ => (),
+    }
+    let selected = options.selection.resolve(registry, options.year).map_err(Failure::usage)?;
+    if selected.len() > 1 && !matches!(options.input, InputSource::Default(_)) {
+        return Err(Failure::usage("Option '--input' requires selecting a single day"))
+    }
+    match options.command {
+        Command::Submit(day, part) => submit::run(options, registry, day, part),
+        Command::Generate(day) => generate::run(options, registry, day),
+        Command::Repl(day) => repl::run(options, registry, day),
+        _ if options.watch => watch::run(options, registry, &selected),
+        Command::Compare => history::run(options, &selected),
+        _ if options.inputs.is_some() => run_inputs(options, registry, &selected),
+        _ => run_days(options, registry, selected),
+    }
 }
 
-fn main() -> Result<(), Error> {
-    let default_day = String::from("13");
-    let arguments: Vec<String> = env::args().collect();
-    let solver = match arguments.get(1).unwrap_or(&default_day).as_str() {
-        "1" => day1::solve,
-        "2" => day2::solve,
-        "3" => day3::solve,
-        "4" => day4::solve,
-        "5" => day5::solve,
-        "6" => day6::solve,
-        "7" => day7::solve,
-        "8" => day8::solve,
-        "9" => day9::solve,
-        "10" => day10::solve,
-        "11" => day11::solve,
-        "12" => day12::solve,
-        "13" => day13::solve,
-        x => panic!("Unknown Exercise {}", x)
+/// Run, verify, benchmark or report on the `selected` days, depending on the command of the `options`
+fn run_days(options: &Options, registry: &'static Registry, selected: Vec<(u32, Vec<Part>)>) -> Result<(), Failure> {
+    let year = options.year;
+    let answers = match options.command {
+        Command::Verify => Answers::load(&options.answers).map_err(
+            |error| Failure::failed(format!("Cannot read answers from {}: {}", options.answers.display(), error))
+        )?,
+        _ => Answers::default(),
     };
-    run_solver(solver)?;
+    let verbose = options.command == Command::Run && options.format == Format::Text;
+    let workers = options.workers();
+    let build = options.bench.map(|_| history::build_id()).transpose().map_err(
+        |error| Failure::failed(format!("Cannot identify the build for the history: {}", error))
+    )?;
+    let (mut results, mut outcomes, mut failed) = (vec![], vec![], false);
+    let start = Instant::now();
+    pool::run_ordered(selected, workers, |(day, parts)| {
+        let solver = registry.get(year, day).unwrap();
+        let (bench, input) = (options.bench, options.input.clone());
+        let result = with_timeout(options.timeout, move || catch_panics(|| match bench {
+            Some(runs) => bench_solver(solver, &parts, &input, runs).map(
+                |(result, bench)| (result, Some(bench))
+            ),
+            None => run_solver(solver, &parts, &input).map(|result| (result, None)),
+        }));
+        (day, result)
+    }, |(day, result)| {
+        outcomes.push((day, Outcome::of(&result)));
+        match result {
+            Ok((result, bench)) => {
+                if verbose {
+                    match &bench {
+                        Some(bench) => print_bench(&result, bench),
+                        None => print_result(&result),
+                    }
+                }
+                if let Some(build) = &build {
+                    if let Err(error) = history::append(&options.history, &history::records(build, &result)) {
+                        eprintln!("Cannot add to history in {}: {}", options.history.display(), error);
+                        failed = true;
+                    }
+                }
+                results.push(result)
+            },
+            Err(error) => {
+                eprintln!("Day {} failed: {}", day, error);
+                failed = true;
+            }
+        }
+    });
+    match (options.command, options.format) {
+        (Command::Verify, _) => failed |= !print_verification(&results, &answers),
+        (Command::Run, Format::Text) if results.len() > 1 => {
+            print_summary(&results);
+            println!("[> Wall time {} on {} threads <]", format_duration(start.elapsed()), workers);
+        },
+        (Command::Run, Format::Json) => println!("{}", json::day_results(year, &results)),
+        (Command::Report, _) => {
+            let content = match options.output.extension().and_then(|extension| extension.to_str()) {
+                Some("html") | Some("htm") => report::html(year, &results),
+                _ => report::markdown(year, &results),
+            };
+            match fs::write(&options.output, content) {
+                Ok(()) => println!("Wrote report to {}", options.output.display()),
+                Err(error) => {
+                    eprintln!("Cannot write report to {}: {}", options.output.display(), error);
+                    failed = true;
+                },
+            }
+        },
+        _ => (),
+    }
+    if outcomes.len() > 1 && options.format == Format::Text {
+        print_outcomes(&outcomes);
+    }
+    if failed {
+        return Err(Failure::reported())
+    }
     Ok(())
 }
@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+/// Number of worker threads to use by default
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map(|workers| workers.get()).unwrap_or(1)
+}
+
+/// Run `work` for all `jobs` on up to `workers` threads, `emit`ting results in order of `jobs`
+///
+/// Each result is emitted as soon as it and all results of preceding jobs are available.
+pub fn run_ordered<T, R, W, E>(jobs: Vec<T>, workers: usize, work: W, mut emit: E)
+where T: Send, R: Send, W: Fn(T) -> R + Sync, E: FnMut(R) {
+    let workers = workers.clamp(1, jobs.len().max(1));
+    if workers == 1 {
+        jobs.into_iter().map(work).for_each(emit);
+        return
+    }
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let (queue, work, sender) = (&queue, &work, sender.clone());
+            scope.spawn(move || loop {
+                // release the queue before working, so that other workers may take jobs
+                let job = queue.lock().unwrap().next();
+                match job {
+                    Some((index, job)) => if sender.send((index, work(job))).is_err() {
+                        break
+                    },
+                    None => break,
+                }
+            });
+        }
+        drop(sender);
+        let (mut pending, mut next) = (BTreeMap::new(), 0);
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next) {
+                emit(result);
+                next += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn emits_in_order_of_jobs() {
+        let finished = Mutex::new(Vec::new());
+        let mut emitted = Vec::new();
+        // early jobs take longest, so they finish last
+        run_ordered((0..4).collect(), 4, |job: u64| {
+            thread::sleep(Duration::from_millis(50 * (4 - job)));
+            finished.lock().unwrap().push(job);
+            job * 10
+        }, |result| emitted.push(result));
+        assert_eq!(finished.into_inner().unwrap(), [3, 2, 1, 0]);
+        assert_eq!(emitted, [0, 10, 20, 30]);
+    }
+
+    #[test]
+    fn more_jobs_than_workers() {
+        for workers in [0, 1, 3, 100] {
+            let mut emitted = Vec::new();
+            run_ordered((0..20).collect(), workers, |job: u64| {
+                thread::sleep(Duration::from_millis(job % 3));
+                job
+            }, |result| emitted.push(result));
+            assert_eq!(emitted, (0..20).collect::<Vec<_>>());
+        }
+    }
+}
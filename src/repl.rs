@@ -0,0 +1,116 @@
+use std::io::{self, BufRead, Write};
+use std::time::{Duration, Instant};
+
+use crate::cli::{Failure, Options};
+use crate::error::AocError;
+use crate::runner::{catch_panics, format_duration, InputSource};
+use crate::solution::{Parsed, Part, Registry, Solver};
+
+
+/// Commands of every day, as usage and description
+const BUILTIN: [(&str, &str); 5] = [
+    ("part1", "solve the first part"),
+    ("part2", "solve the second part"),
+    ("history", "show the results of all commands so far"),
+    ("help", "show the commands"),
+    ("quit", "leave, as does the end of input"),
+];
+
+/// A command that was run, with its answer or error and how long it took
+struct Entry {
+    line: String,
+    outcome: Result<String, String>,
+    elapsed: Duration,
+}
+
+/// Print the `entry` with its `number`, counting from 1
+fn print_entry(number: usize, entry: &Entry) {
+    match &entry.outcome {
+        Ok(answer) => println!("[{}] {} => {} ({})", number, entry.line, answer, format_duration(entry.elapsed)),
+        Err(error) => println!("[{}] {} => failed: {} ({})", number, entry.line, error, format_duration(entry.elapsed)),
+    }
+}
+
+fn print_help(solver: &dyn Solver) {
+    let commands: Vec<_> = solver.commands().iter().chain(BUILTIN.iter()).collect();
+    let width = commands.iter().map(|(usage, _)| usage.len()).max().unwrap_or(0);
+    for (usage, description) in commands {
+        println!("  {:<w$}  {}", usage, description, w = width);
+    }
+}
+
+/// Run the command `line` on the `parsed` input of a `solver`, or `None` if there is no such command
+fn run_command(solver: &dyn Solver, parsed: &Parsed, line: &str) -> Option<Result<String, AocError>> {
+    let (command, arguments) = line.split_once(char::is_whitespace).map_or(
+        (line, ""), |(command, arguments)| (command, arguments.trim())
+    );
+    let part = match command {
+        "part1" => Some(Part::One),
+        "part2" => Some(Part::Two),
+        _ => None,
+    };
+    if let Some(part) = part {
+        return Some(match arguments {
+            "" => catch_panics(|| solver.solve(parsed, part)),
+            _ => Err(AocError::invalid(format!("command '{}' takes no arguments", command))),
+        })
+    }
+    solver.commands().iter().find(|(usage, _)| usage.split_whitespace().next() == Some(command)).map(
+        |_| catch_panics(|| solver.explore(parsed, command, arguments))
+    )
+}
+
+/// Explore the input `source` of a `solver` interactively, reading commands from `commands`
+///
+/// The input is read and parsed once, after which each command runs on the parsed input and
+/// shows its result and timing. The results of all commands are numbered and kept for `history`.
+pub fn repl(solver: &dyn Solver, source: &InputSource, commands: &mut dyn BufRead) -> Result<(), AocError> {
+    let day = solver.day();
+    let raw = source.read(day)?;
+    let pre = Instant::now();
+    let parsed = solver.parse(&mut &raw[..]).map_err(|error| error.in_file(&source.name(day)))?;
+    println!("Parsed {} in {}; enter 'help' for the commands", source.name(day), format_duration(pre.elapsed()));
+    let mut history = vec![];
+    let mut line = String::new();
+    loop {
+        print!("day{}> ", day);
+        io::stdout().flush()?;
+        line.clear();
+        if commands.read_line(&mut line)? == 0 {
+            println!();
+            return Ok(())
+        }
+        match line.trim() {
+            "" => (),
+            "quit" => return Ok(()),
+            "help" => print_help(solver),
+            "history" => for (index, entry) in history.iter().enumerate() {
+                print_entry(index + 1, entry);
+            },
+            command => {
+                let pre = Instant::now();
+                let outcome = match run_command(solver, &parsed, command) {
+                    Some(outcome) => outcome,
+                    None => {
+                        eprintln!("Unknown command '{}'; enter 'help' for the commands", command);
+                        continue
+                    },
+                };
+                let outcome = outcome.map_err(|error| error.to_string());
+                let entry = Entry {line: String::from(command), outcome, elapsed: pre.elapsed()};
+                print_entry(history.len() + 1, &entry);
+                history.push(entry);
+            },
+        }
+    }
+}
+
+/// Explore the input of `day` interactively, with commands from stdin
+pub fn run(options: &Options, registry: &Registry, day: u32) -> Result<(), Failure> {
+    if matches!(options.input, InputSource::Stdin) {
+        return Err(Failure::usage("Command 'repl' reads commands from stdin, so it cannot read the input from there"))
+    }
+    repl(registry.get(options.year, day).unwrap(), &options.input, &mut io::stdin().lock()).map_err(
+        |error| Failure::failed(format!("Day {} failed: {}", day, error))
+    )
+}
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use crate::alloc::{format_bytes, AllocStats};
+use crate::runner::{format_duration, DayResult};
+use crate::solution::Part;
+
+
+/// Width of the longest bar of a runtime chart, in characters
+const BAR_WIDTH: usize = 40;
+
+/// Column headings of the report tables
+const HEADINGS: [&str; 9] = ["Day", "Part 1", "Part 2", "Parse", "Time 1", "Time 2", "Elapsed", "Allocations", "Peak"];
+
+/// Cells of the table row of a `result`, matching `HEADINGS`
+fn row(result: &DayResult) -> Vec<String> {
+    let mut cells = vec![result.day.to_string()];
+    for part in Part::ALL.iter() {
+        cells.push(result.part(*part).map(|part| part.answer.clone()).unwrap_or_else(|| String::from("-")));
+    }
+    cells.push(format_duration(result.parse));
+    for part in Part::ALL.iter() {
+        cells.push(result.part(*part).map(|part| format_duration(part.elapsed)).unwrap_or_else(|| String::from("-")));
+    }
+    cells.push(format_duration(result.elapsed()));
+    match result.alloc {
+        Some(AllocStats {allocations, bytes, peak}) => {
+            cells.push(format!("{} ({})", allocations, format_bytes(bytes)));
+            cells.push(format_bytes(peak));
+        },
+        None => cells.extend(vec![String::from("-"); 2]),
+    }
+    cells
+}
+
+/// Length of the bar for `elapsed` relative to the `longest` runtime, in eighths of a character
+fn bar_eighths(elapsed: Duration, longest: Duration) -> usize {
+    let fraction = elapsed.as_secs_f64() / longest.as_secs_f64().max(1e-9);
+    ((fraction * (BAR_WIDTH * 8) as f64).round() as usize).max(1)
+}
+
+/// Bar of `eighths` of a character drawn with Unicode block elements
+fn unicode_bar(eighths: usize) -> String {
+    let partial = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+    "█".repeat(eighths / 8) + partial[eighths % 8]
+}
+
+fn total(results: &[DayResult]) -> Duration {
+    results.iter().map(|result| result.elapsed()).sum()
+}
+
+/// Format the `results` of several days of `year` as a Markdown report
+///
+/// The report has a table of answers, timings and allocations and a bar chart of runtimes.
+pub fn markdown(year: u32, results: &[DayResult]) -> String {
+    let mut report = format!("# Advent of Code {}\n\n", year);
+    report += &format!("| {} |\n", HEADINGS.join(" | "));
+    report += &format!("|{}\n", "---:|".repeat(HEADINGS.len()));
+    for result in results {
+        // answers are numbers or words, but must not break the table
+        let cells: Vec<String> = row(result).iter().map(|cell| cell.replace('|', "\\|")).collect();
+        report += &format!("| {} |\n", cells.join(" | "));
+    }
+    report += &format!("\nTotal elapsed time: {}\n\n## Runtimes\n\n```text\n", format_duration(total(results)));
+    let longest = results.iter().map(|result| result.elapsed()).max().unwrap_or_default();
+    for result in results {
+        report += &format!(
+            "Day {:>2} {:<w$} {}\n", result.day, unicode_bar(bar_eighths(result.elapsed(), longest)),
+            format_duration(result.elapsed()), w = BAR_WIDTH + 1
+        );
+    }
+    report += "```\n";
+    report
+}
+
+/// Escape `s` for use as HTML text or attribute value
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Format the `results` of several days of `year` as a self-contained HTML report
+///
+/// The report has the same content as the `markdown` report, with the chart as inline SVG.
+pub fn html(year: u32, results: &[DayResult]) -> String {
+    let mut report = format!(
+        concat!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code {year}</title>\n",
+            "<style>\nbody {{font-family: sans-serif; margin: 2em;}}\n",
+            "table {{border-collapse: collapse;}}\n",
+            "th, td {{border: 1px solid #ccc; padding: 0.2em 0.6em; text-align: right;}}\n",
+            "svg text {{font-size: 12px;}}\n</style>\n</head>\n<body>\n<h1>Advent of Code {year}</h1>\n<table>\n<tr>",
+        ),
+        year = year
+    );
+    for heading in HEADINGS.iter() {
+        report += &format!("<th>{}</th>", heading);
+    }
+    report += "</tr>\n";
+    for result in results {
+        report += "<tr>";
+        for cell in row(result) {
+            report += &format!("<td>{}</td>", escape(&cell));
+        }
+        report += "</tr>\n";
+    }
+    report += &format!(
+        "</table>\n<p>Total elapsed time: {}</p>\n<h2>Runtimes</h2>\n", escape(&format_duration(total(results)))
+    );
+    let (bar_height, label_width, scale) = (20, 60, 10);
+    let longest = results.iter().map(|result| result.elapsed()).max().unwrap_or_default();
+    report += &format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n",
+        label_width + BAR_WIDTH * scale + 100, bar_height * results.len()
+    );
+    for (index, result) in results.iter().enumerate() {
+        let (y, width) = (index * bar_height, bar_eighths(result.elapsed(), longest) * scale / 8);
+        report += &format!(
+            concat!(
+                "<text x=\"0\" y=\"{}\">Day {}</text>",
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#4a7ebb\"/>",
+                "<text x=\"{}\" y=\"{}\">{}</text>\n",
+            ),
+            y + 15, result.day, label_width, y + 3, width, bar_height - 6,
+            label_width + width + 5, y + 15, escape(&format_duration(result.elapsed())),
+        );
+    }
+    report += "</svg>\n</body>\n</html>\n";
+    report
+}
@@ -0,0 +1,414 @@
+use std::cell::{Cell, RefCell};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Error, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Instant, Duration};
+
+use crate::alloc::{self, format_bytes, AllocStats};
+use crate::cli::{Command, Failure, Options};
+use crate::error::AocError;
+use crate::pool;
+use crate::solution::{Registry, Solver, Part};
+
+
+/// Format a duration nicely
+pub fn format_duration(delta: Duration) -> String {
+    let mut fractional = delta.as_secs_f64();
+    for symbol in ["s", "ms", "μs", "ns"].iter() {
+        if fractional > 0.5 {
+            return format!("{:.2} {}", fractional, symbol);
+        }
+        else {
+            fractional *= 1000.0;
+        }
+    }
+    format!("{:?}", delta)
+}
+
+/// Where to read the puzzle input of a day from
+#[derive(Debug, Clone)]
+pub enum InputSource {
+    /// The day's own file in a data directory
+    Default(PathBuf),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Path of the input used for `day` by default, in the `data` directory
+    pub fn default_path(data: &Path, day: u32) -> PathBuf {
+        data.join(format!("day{}.txt", day))
+    }
+
+    /// Path of the small example input of `day` in the `data` directory, which need not exist
+    pub fn example_path(data: &Path, day: u32) -> PathBuf {
+        data.join(format!("day{}.example.txt", day))
+    }
+
+    /// Path of the file read for `day`, unless reading from stdin
+    pub fn path(&self, day: u32) -> Option<PathBuf> {
+        match self {
+            InputSource::Default(data) => Some(InputSource::default_path(data, day)),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    /// Name of the input for `day` for use in messages
+    pub fn name(&self, day: u32) -> String {
+        match self {
+            InputSource::Default(data) => InputSource::default_path(data, day).display().to_string(),
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => String::from("<stdin>"),
+        }
+    }
+
+    /// Open the input for `day`
+    pub fn open(&self, day: u32) -> Result<Box<dyn BufRead>, Error> {
+        Ok(match self {
+            InputSource::Default(data) => Box::new(BufReader::new(File::open(InputSource::default_path(data, day))?)),
+            InputSource::File(path) => Box::new(BufReader::new(File::open(path)?)),
+            InputSource::Stdin => Box::new(io::stdin().lock()),
+        })
+    }
+
+    /// Read the complete input for `day`
+    pub fn read(&self, day: u32) -> Result<Vec<u8>, AocError> {
+        let mut raw = vec![];
+        self.open(day).and_then(|mut input| input.read_to_end(&mut raw)).map_err(
+            |error| Error::new(error.kind(), format!("{}: {}", self.name(day), error))
+        )?;
+        Ok(raw)
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = String;
+
+    /// Read a literal such as `data/2020/day7.txt` or `-` for stdin
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err(String::from("Empty input path")),
+            "-" => Ok(InputSource::Stdin),
+            path => Ok(InputSource::File(PathBuf::from(path))),
+        }
+    }
+}
+
+/// All files in the `directory` of inputs, in order of their names
+pub fn input_files(directory: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut files = vec![];
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Answer and timing of solving one part
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: Part,
+    pub answer: String,
+    pub elapsed: Duration,
+    /// Allocations made while solving, if they were counted
+    pub alloc: Option<AllocStats>,
+}
+
+/// Answers and timings of running one day
+#[derive(Debug, Clone)]
+pub struct DayResult {
+    pub day: u32,
+    /// Time taken to parse the input
+    pub parse: Duration,
+    /// Result of each part that was run
+    pub parts: Vec<PartResult>,
+    /// Number of timed runs; timings are medians if there are several
+    pub runs: usize,
+    /// Allocations made while parsing, if they were counted
+    pub parse_alloc: Option<AllocStats>,
+    /// Allocations made while parsing and solving all parts, if they were counted
+    pub alloc: Option<AllocStats>,
+}
+
+impl DayResult {
+    /// Result of `part`, if it was run
+    pub fn part(&self, part: Part) -> Option<&PartResult> {
+        self.parts.iter().find(|result| result.part == part)
+    }
+
+    /// Time taken for parsing and all parts
+    pub fn elapsed(&self) -> Duration {
+        self.parse + self.parts.iter().map(|result| result.elapsed).sum::<Duration>()
+    }
+}
+
+/// Parse the `input` and solve the `parts` of a `solver`, without printing anything
+///
+/// Allocations are counted per phase if counting is enabled.
+pub fn execute(solver: &dyn Solver, parts: &[Part], input: &mut dyn BufRead) -> Result<DayResult, AocError> {
+    let (result, alloc) = alloc::measure(|| {
+        let pre = Instant::now();
+        let (parsed, parse_alloc) = alloc::measure(|| solver.parse(input));
+        let (parsed, parse) = (parsed?, pre.elapsed());
+        let parts = parts.iter().map(|part| {
+            let pre = Instant::now();
+            let (answer, alloc) = alloc::measure(|| solver.solve(&parsed, *part));
+            Ok(PartResult {part: *part, answer: answer?, elapsed: pre.elapsed(), alloc})
+        }).collect::<Result<_, AocError>>()?;
+        Ok(DayResult {day: solver.day(), parse, parts, runs: 1, parse_alloc, alloc: None})
+    });
+    result.map(|result| DayResult {alloc, ..result})
+}
+
+/// Print the answers of a `result` as they are solved
+pub fn print_answers(result: &DayResult) {
+    println!("Day {}", result.day);
+    for part in &result.parts {
+        println!("Part {}: {}", part.part.number(), part.answer);
+    }
+}
+
+/// Run the `parts` of a `solver` on its input `source`
+///
+/// The input is read completely before parsing, so that timings do not include I/O.
+pub fn run_solver(solver: &dyn Solver, parts: &[Part], source: &InputSource) -> Result<DayResult, AocError> {
+    let raw = source.read(solver.day())?;
+    execute(solver, parts, &mut &raw[..]).map_err(|error| error.in_file(&source.name(solver.day())))
+}
+
+/// Run `work` on its own thread, giving up if it does not finish within the `timeout`, if any
+///
+/// A thread which timed out cannot be stopped; it keeps running in the background until the
+/// process exits. Panics of `work` are passed on to the caller.
+pub fn with_timeout<R, W>(timeout: Option<Duration>, work: W) -> Result<R, AocError>
+where R: Send + 'static, W: FnOnce() -> Result<R, AocError> + Send + 'static {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return work(),
+    };
+    let (sender, receiver) = mpsc::channel();
+    let worker = thread::spawn(move || {
+        // the receiver is gone if the work timed out, so there is nobody to tell
+        let _ = sender.send(work());
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(AocError::TimedOut(timeout)),
+        Err(RecvTimeoutError::Disconnected) => match worker.join() {
+            Err(panic) => std::panic::resume_unwind(panic),
+            Ok(()) => unreachable!("worker finished without sending its result"),
+        },
+    }
+}
+
+thread_local! {
+    /// Whether panics on this thread are caught by `catch_panics`
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// Source location of the latest caught panic on this thread
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Replace the panic hook so that caught panics are recorded instead of printed
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(Cell::get) {
+                PANIC_LOCATION.with(|location| *location.borrow_mut() = info.location().map(|at| at.to_string()));
+            } else {
+                default(info)
+            }
+        }));
+    });
+}
+
+/// Run `work`, turning any panic into an error with its message and location
+pub fn catch_panics<R, W: FnOnce() -> Result<R, AocError>>(work: W) -> Result<R, AocError> {
+    install_panic_hook();
+    let catching = CATCHING.with(|flag| flag.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(work));
+    CATCHING.with(|flag| flag.set(catching));
+    result.unwrap_or_else(|panic| {
+        let message = match (panic.downcast_ref::<&str>(), panic.downcast_ref::<String>()) {
+            (Some(message), _) => String::from(*message),
+            (_, Some(message)) => message.clone(),
+            _ => String::from("unknown panic"),
+        };
+        let location = PANIC_LOCATION.with(|location| location.borrow_mut().take());
+        Err(AocError::Panicked {message, location})
+    })
+}
+
+/// How running a day ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    Failed,
+    Panicked,
+}
+
+impl Outcome {
+    pub fn of<R>(result: &Result<R, AocError>) -> Self {
+        match result {
+            Ok(_) => Outcome::Passed,
+            Err(AocError::Panicked {..}) => Outcome::Panicked,
+            Err(_) => Outcome::Failed,
+        }
+    }
+}
+
+/// Print which of several days passed, failed or panicked
+pub fn print_outcomes(outcomes: &[(u32, Outcome)]) {
+    let days = |outcome| outcomes.iter().filter(
+        |(_, other)| *other == outcome
+    ).map(|(day, _)| day.to_string()).collect::<Vec<_>>();
+    let (passed, failed, panicked) = (days(Outcome::Passed), days(Outcome::Failed), days(Outcome::Panicked));
+    println!("[> {} passed | {} failed | {} panicked <]", passed.len(), failed.len(), panicked.len());
+    for (label, days) in [("Passed", passed), ("Failed", failed), ("Panicked", panicked)].iter() {
+        if !days.is_empty() {
+            println!("{:<9} {}", format!("{}:", label), days.join(", "));
+        }
+    }
+}
+
+/// Print the answers of a `result` and its time per phase
+pub fn print_result(result: &DayResult) {
+    print_answers(result);
+    let mut phases = vec![format!("Parse {}", format_duration(result.parse))];
+    for part in &result.parts {
+        phases.push(format!("Part {} {}", part.part.number(), format_duration(part.elapsed)));
+    }
+    println!("[> {} | Elapsed {} <]", phases.join(" | "), format_duration(result.elapsed()));
+    print_allocations(result);
+}
+
+/// Print the allocations of a `result` per phase, if they were counted
+pub fn print_allocations(result: &DayResult) {
+    let format = |stats: AllocStats| format!(
+        "{} allocs, {}, peak {}", stats.allocations, format_bytes(stats.bytes), format_bytes(stats.peak)
+    );
+    let mut phases = vec![];
+    if let Some(stats) = result.parse_alloc {
+        phases.push(format!("Parse {}", format(stats)));
+    }
+    for part in &result.parts {
+        if let Some(stats) = part.alloc {
+            phases.push(format!("Part {} {}", part.part.number(), format(stats)));
+        }
+    }
+    if let Some(stats) = result.alloc {
+        phases.push(format!("Total {}", format(stats)));
+        println!("[> {} <]", phases.join(" | "));
+    }
+}
+
+/// Print a table of the answers and timings of several days
+pub fn print_summary(results: &[DayResult]) {
+    let answer = |result: &DayResult, part| match result.part(part) {
+        Some(part) => part.answer.clone(),
+        None => String::from("-"),
+    };
+    let time = |result: &DayResult, part| match result.part(part) {
+        Some(part) => format_duration(part.elapsed),
+        None => String::from("-"),
+    };
+    let width = results.iter().flat_map(
+        |result| Part::ALL.iter().map(move |part| answer(result, *part).len())
+    ).max().unwrap_or(0).max(6);
+    println!(
+        "{:>3} | {:<w$} | {:<w$} | {:>10} | {:>10} | {:>10} | {:>10}",
+        "Day", "Part 1", "Part 2", "Parse", "Time 1", "Time 2", "Elapsed", w = width
+    );
+    println!(
+        "{:-<3}-+-{:-<w$}-+-{:-<w$}-+-{:-<10}-+-{:-<10}-+-{:-<10}-+-{:-<10}",
+        "", "", "", "", "", "", "", w = width
+    );
+    for result in results {
+        println!(
+            "{:>3} | {:<w$} | {:<w$} | {:>10} | {:>10} | {:>10} | {:>10}",
+            result.day, answer(result, Part::One), answer(result, Part::Two),
+            format_duration(result.parse), time(result, Part::One), time(result, Part::Two),
+            format_duration(result.elapsed()), w = width
+        );
+    }
+    let total: Duration = results.iter().map(|result| result.elapsed()).sum();
+    println!(
+        "{:>3} | {:<w$} | {:<w$} | {:>10} | {:>10} | {:>10} | {:>10}",
+        "", "", "", "", "", "Total", format_duration(total), w = width
+    );
+}
+
+/// Print a table of the answers and timings of running one day on several named inputs
+///
+/// Inputs which failed show how they failed instead of answers.
+pub fn print_input_summary(rows: &[(String, Result<DayResult, AocError>)]) {
+    let cells = |result: &Result<DayResult, AocError>| match result {
+        Ok(result) => Part::ALL.iter().map(|part| match result.part(*part) {
+            Some(part) => part.answer.clone(),
+            None => String::from("-"),
+        }).chain(std::iter::once(format_duration(result.elapsed()))).collect::<Vec<_>>(),
+        Err(_) => {
+            let outcome = match Outcome::of(result) {
+                Outcome::Panicked => "panicked",
+                _ => "failed",
+            };
+            vec![String::from(outcome), String::from("-"), String::from("-")]
+        },
+    };
+    let rows: Vec<(&str, Vec<String>)> = rows.iter().map(|(name, result)| (name.as_str(), cells(result))).collect();
+    let input_width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max(5);
+    let width = rows.iter().flat_map(|(_, cells)| cells[..2].iter().map(String::len)).max().unwrap_or(0).max(6);
+    println!("{:<i$} | {:<w$} | {:<w$} | {:>10}", "Input", "Part 1", "Part 2", "Elapsed", i = input_width, w = width);
+    println!("{:-<i$}-+-{:-<w$}-+-{:-<w$}-+-{:-<10}", "", "", "", "", i = input_width, w = width);
+    for (name, cells) in &rows {
+        println!(
+            "{:<i$} | {:<w$} | {:<w$} | {:>10}", name, cells[0], cells[1], cells[2], i = input_width, w = width
+        );
+    }
+}
+
+/// Run the single `selected` day on every input in the inputs directory of the `options`
+///
+/// Each input runs on its own, so that a failing input does not stop the others.
+pub fn run_inputs(
+    options: &Options, registry: &'static Registry, selected: &[(u32, Vec<Part>)]
+) -> Result<(), Failure> {
+    let directory = options.inputs.as_ref().expect("an inputs directory");
+    if selected.len() != 1 || options.command != Command::Run || options.bench.is_some() {
+        return Err(Failure::usage("Option '--inputs' requires running a single day"))
+    }
+    if !matches!(options.input, InputSource::Default(_)) {
+        return Err(Failure::usage("Option '--inputs' cannot be combined with '--input'"))
+    }
+    let files = input_files(directory).map_err(
+        |error| Failure::failed(format!("Cannot read inputs from {}: {}", directory.display(), error))
+    )?;
+    let (day, parts) = &selected[0];
+    let solver = registry.get(options.year, *day).unwrap();
+    let mut rows = vec![];
+    pool::run_ordered(files, options.workers(), |path| {
+        let (source, parts) = (InputSource::File(path), parts.clone());
+        let name = source.name(*day);
+        (name, with_timeout(options.timeout, move || catch_panics(|| run_solver(solver, &parts, &source))))
+    }, |(name, result)| {
+        if let Err(error) = &result {
+            eprintln!("Input {} failed: {}", name, error);
+        }
+        rows.push((name, result))
+    });
+    print_input_summary(&rows);
+    match rows.iter().any(|(_, result)| result.is_err()) {
+        true => Err(Failure::reported()),
+        false => Ok(()),
+    }
+}
@@ -0,0 +1,254 @@
+use std::fs::{self, OpenOptions};
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use crate::cli::{Failure, Options};
+use crate::runner::InputSource;
+
+
+/// Scaffold `day` of the year of the `options`, showing the files created or updated
+pub fn run(options: &Options, day: u32) -> Result<(), Failure> {
+    let changed = new_day(Path::new("."), &options.data, options.year, day).map_err(
+        |error| Failure::failed(format!("Cannot add day {}: {}", day, error))
+    )?;
+    for path in changed {
+        println!("Created or updated {}", path.display());
+    }
+    Ok(())
+}
+
+/// Module skeleton of a new day, with `{year}` and `{day}` to be replaced by their numbers
+///
+/// The test stub reads the example input, whose path literal replaces `{example}`.
+const TEMPLATE: &str = "\
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::error::AocError;
+use crate::solution::Solution;
+use crate::utility::parse_lines;
+
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u32 = {year};
+    const DAY: u32 = {day};
+    type Input = Vec<Line>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse_lines(input)
+    }
+
+    fn part1(&self, lines: &Self::Input) -> Result<usize, AocError> {
+        Ok(lines.len())
+    }
+
+    fn part2(&self, _lines: &Self::Input) -> Result<usize, AocError> {
+        Err(AocError::invalid(\"not solved yet\"))
+    }
+}
+
+#[derive(Debug)]
+pub struct Line(pub String);
+
+impl FromStr for Line {
+    type Err = AocError;
+
+    /// Read a literal such as `...`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Line(String::from(s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use super::*;
+
+    /// Answer given for the example, to be filled in
+    const EXAMPLE_PART1: Option<usize> = None;
+
+    #[test]
+    fn solves_example() {
+        let file = File::open({example}).expect(\"the example input of the day\");
+        let lines = Puzzle.parse(&mut BufReader::new(file)).unwrap();
+        assert_eq!(Puzzle.part1(&lines).unwrap(), EXAMPLE_PART1.expect(\"the answer given for the example\"));
+    }
+}
+";
+
+/// Module skeleton of a new year with its first day, with `{year}` and `{day}` to be replaced by their numbers
+const YEAR_TEMPLATE: &str = "\
+//! Solutions for Advent of Code {year}
+pub mod day{day};
+
+use crate::solution::Registry;
+
+
+/// Add all days of the year with a solution to the `registry`
+pub fn register(registry: &mut Registry) {
+    registry
+        .register(day{day}::Puzzle);
+}
+";
+
+/// Add a skeleton for `day` of `year` to the project at `root`, returning all created or changed files
+///
+/// This creates the module `src/yYYYY/dayN.rs` and registers it in `src/yYYYY/mod.rs`, and creates
+/// empty `dayN.txt` input and `dayN.example.txt` example files in the `data` directory of the year.
+/// The module of a year without any days yet is created and registered in `src/lib.rs`.
+pub fn new_day(root: &Path, data: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::new(ErrorKind::InvalidInput, format!("Day {} is not in the advent calendar", day)))
+    }
+    let directory = root.join(format!("src/y{}", year));
+    let module = directory.join(format!("day{}.rs", day));
+    if module.exists() {
+        return Err(Error::new(ErrorKind::AlreadyExists, format!("{} already exists", module.display())))
+    }
+    let example = format!("{:?}", InputSource::example_path(data, day).display().to_string());
+    let fill = |template: &str| template.replace("{year}", &year.to_string()).replace("{day}", &day.to_string())
+        .replace("{example}", &example);
+    let parent = directory.join("mod.rs");
+    let mut changed = vec![module.clone(), parent.clone()];
+    if parent.exists() {
+        let registered = register_day(&fs::read_to_string(&parent)?, day)?;
+        fs::write(&module, fill(TEMPLATE))?;
+        fs::write(&parent, registered)?;
+    } else {
+        let lib = root.join("src/lib.rs");
+        let registered = register_year(&fs::read_to_string(&lib)?, year)?;
+        fs::create_dir_all(&directory)?;
+        fs::write(&module, fill(TEMPLATE))?;
+        fs::write(&parent, fill(YEAR_TEMPLATE))?;
+        fs::write(&lib, registered)?;
+        changed.push(lib);
+    }
+    fs::create_dir_all(root.join(data))?;
+    for data in [InputSource::default_path(data, day), InputSource::example_path(data, day)].iter() {
+        let data = root.join(data);
+        // never truncate inputs that were already downloaded or written
+        OpenOptions::new().write(true).create(true).truncate(false).open(&data)?;
+        changed.push(data);
+    }
+    Ok(changed)
+}
+
+/// Add `day` to the module declarations and registrations of the `year` module source
+fn register_day(year: &str, day: u32) -> Result<String, Error> {
+    let mut lines: Vec<String> = year.lines().map(String::from).collect();
+    insert_after(&mut lines, "pub mod day", day, &format!("pub mod day{};", day))?;
+    let registration = insert_after(
+        &mut lines, "        .register(day", day, &format!("        .register(day{}::Puzzle)", day)
+    )?;
+    // the last registration ends the chain of registrations
+    if lines[registration - 1].ends_with(';') {
+        lines[registration - 1].pop();
+        lines[registration].push(';');
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Add the module of `year` to the module declarations and registry of the `lib` source
+fn register_year(lib: &str, year: u32) -> Result<String, Error> {
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+    insert_after(&mut lines, "pub mod y", year, &format!("pub mod y{};", year))?;
+    insert_after(&mut lines, "    y", year, &format!("    y{}::register(&mut registry);", year))?;
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Insert a `line` after the last line with `prefix` followed by a number before `number`
+///
+/// Returns the index of the inserted line.
+fn insert_after(lines: &mut Vec<String>, prefix: &str, number: u32, line: &str) -> Result<usize, Error> {
+    let number_of = |line: &str| line.strip_prefix(prefix).and_then(
+        |tail| tail.split(|c: char| !c.is_ascii_digit()).next()?.parse::<u32>().ok()
+    );
+    let index = lines.iter().rposition(
+        |line| number_of(line).is_some_and(|other| other < number)
+    ).map(|index| index + 1).or_else(
+        || lines.iter().position(|line| number_of(line).is_some())
+    ).ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("No lines of '{}' to extend", prefix.trim())))?;
+    lines.insert(index, String::from(line));
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YEAR: &str = "\
+//! Solutions for Advent of Code 2020
+pub mod day2;
+pub mod day5;
+
+use crate::solution::Registry;
+
+
+/// Add all days of the year with a solution to the `registry`
+pub fn register(registry: &mut Registry) {
+    registry
+        .register(day2::Puzzle)
+        .register(day5::Puzzle::default());
+}
+";
+
+    const LIB: &str = "\
+pub mod solution;
+pub mod y2020;
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+    y2020::register(&mut registry);
+    registry
+}
+";
+
+    fn lines(source: &str) -> Vec<String> {
+        source.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn inserts_by_number() {
+        let mut days = lines("pub mod day2;\npub mod day5;\npub mod day10;\n");
+        assert_eq!(insert_after(&mut days, "pub mod day", 7, "pub mod day7;").unwrap(), 2);
+        assert_eq!(insert_after(&mut days, "pub mod day", 1, "pub mod day1;").unwrap(), 0);
+        assert_eq!(insert_after(&mut days, "pub mod day", 12, "pub mod day12;").unwrap(), 5);
+        let numbers: Vec<&str> = days.iter().map(|line| &line[11..line.len() - 1]).collect();
+        assert_eq!(numbers, ["1", "2", "5", "7", "10", "12"]);
+        assert!(insert_after(&mut days, "pub mod y", 2021, "pub mod y2021;").is_err());
+    }
+
+    #[test]
+    fn registers_days() {
+        let middle = register_day(YEAR, 3).unwrap();
+        assert!(middle.contains("pub mod day2;\npub mod day3;\npub mod day5;\n"));
+        assert!(middle.contains("        .register(day2::Puzzle)\n        .register(day3::Puzzle)\n"));
+        assert!(middle.contains("        .register(day3::Puzzle)\n        .register(day5::Puzzle::default());\n"));
+        let first = register_day(YEAR, 1).unwrap();
+        assert!(first.contains("pub mod day1;\npub mod day2;\n"));
+        assert!(first.contains("    registry\n        .register(day1::Puzzle)\n        .register(day2::Puzzle)\n"));
+        // the last registration takes over the semicolon ending the chain
+        let last = register_day(YEAR, 9).unwrap();
+        assert!(last.contains("pub mod day5;\npub mod day9;\n\n"));
+        assert!(last.contains("        .register(day5::Puzzle::default())\n        .register(day9::Puzzle);\n}\n"));
+        assert!(register_day("pub fn register() {}", 1).is_err());
+    }
+
+    #[test]
+    fn registers_years() {
+        let registered = register_year(LIB, 2021).unwrap();
+        assert!(registered.contains("pub mod y2020;\npub mod y2021;\n"));
+        assert!(registered.contains(
+            "    y2020::register(&mut registry);\n    y2021::register(&mut registry);\n    registry\n"
+        ));
+        let earlier = register_year(LIB, 2019).unwrap();
+        assert!(earlier.contains("pub mod solution;\npub mod y2019;\npub mod y2020;\n"));
+        assert!(earlier.contains("    y2019::register(&mut registry);\n    y2020::register(&mut registry);\n"));
+    }
+}
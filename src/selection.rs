@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::solution::{Part, Registry};
+
+/// Days and parts selected to run, such as `all`, `latest`, `3-9`, `1,4,8` or `7:2`
+#[derive(Debug)]
+pub struct Selection(Vec<Item>);
+
+/// One comma separated item of a selection, such as `3-9:1`
+#[derive(Debug)]
+struct Item {
+    days: Days,
+    parts: Vec<Part>,
+}
+
+#[derive(Debug)]
+enum Days {
+    All,
+    /// The last day with a solution
+    Latest,
+    /// Inclusive range of days; a single day is a range of one
+    Range(u32, u32),
+}
+
+impl Selection {
+    /// The selected days of `year` with their parts, in order of days
+    ///
+    /// Fails with a message if the selection refers to days or a year without a solver.
+    pub fn resolve(&self, registry: &Registry, year: u32) -> Result<Vec<(u32, Vec<Part>)>, String> {
+        if registry.days(year).next().is_none() {
+            return Err(format!("There are no solutions for {}, known years are {:?}", year, registry.years()))
+        }
+        let mut selected = BTreeMap::<u32, Vec<Part>>::new();
+        let mut unknown = vec![];
+        for item in &self.0 {
+            let days: Vec<u32> = match item.days {
+                Days::All => registry.days(year).collect(),
+                Days::Latest => registry.days(year).last().into_iter().collect(),
+                Days::Range(first, last) => (first..=last).collect(),
+            };
+            for day in days {
+                if registry.get(year, day).is_none() {
+                    unknown.push(day);
+                    continue
+                }
+                let parts = selected.entry(day).or_default();
+                for part in &item.parts {
+                    if !parts.contains(part) {
+                        parts.push(*part)
+                    }
+                }
+                parts.sort();
+            }
+        }
+        if unknown.is_empty() {
+            Ok(selected.into_iter().collect())
+        } else {
+            unknown.sort_unstable();
+            unknown.dedup();
+            Err(format!(
+                "Unknown day(s) {:?} of {}, known are {:?}", unknown, year, registry.days(year).collect::<Vec<_>>()
+            ))
+        }
+    }
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    /// Read a literal such as `1-3,7:2,9`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',').map(|item| item.trim().parse()).collect::<Result<_, _>>().map(Selection)
+    }
+}
+
+impl FromStr for Item {
+    type Err = String;
+
+    /// Read a literal such as `all`, `latest`, `7`, `3-9` or `3-9:1`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (days, parts) = match s.find(':') {
+            Some(i) => (&s[..i], vec![s[i + 1..].parse()?]),
+            None => (s, Part::ALL.to_vec()),
+        };
+        let days = match days.find('-') {
+            _ if days == "all" => Days::All,
+            _ if days == "latest" => Days::Latest,
+            Some(i) => Days::Range(parse_day(&days[..i])?, parse_day(&days[i + 1..])?),
+            None => {
+                let day = parse_day(days)?;
+                Days::Range(day, day)
+            },
+        };
+        match days {
+            Days::Range(first, last) if first > last => Err(format!("Empty day range '{}'", s)),
+            days => Ok(Item {days, parts}),
+        }
+    }
+}
+
+/// Read a day of December such as `7`; there are puzzles on days 1 to 25 only
+fn parse_day(s: &str) -> Result<u32, String> {
+    match s.parse() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        Ok(_) => Err(format!("Invalid day '{}', expected 1 to 25", s)),
+        Err(_) => Err(format!("Invalid day '{}'", s)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+
+    use super::*;
+    use crate::error::AocError;
+    use crate::solution::Solution;
+
+    /// Solution of `DAY` of `YEAR` that solves nothing, as only its registration matters
+    struct Stub<const YEAR: u32, const DAY: u32>;
+
+    impl<const YEAR: u32, const DAY: u32> Solution for Stub<YEAR, DAY> {
+        const YEAR: u32 = YEAR;
+        const DAY: u32 = DAY;
+        type Input = ();
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(&self, _input: &mut dyn BufRead) -> Result<(), AocError> {
+            Ok(())
+        }
+
+        fn part1(&self, _input: &()) -> Result<u32, AocError> {
+            Ok(DAY)
+        }
+
+        fn part2(&self, _input: &()) -> Result<u32, AocError> {
+            Ok(DAY)
+        }
+    }
+
+    fn registry() -> Registry {
+        let mut registry = Registry::new();
+        registry
+            .register(Stub::<2019, 1>)
+            .register(Stub::<2020, 1>)
+            .register(Stub::<2020, 2>)
+            .register(Stub::<2020, 3>)
+            .register(Stub::<2020, 4>)
+            .register(Stub::<2020, 5>)
+            .register(Stub::<2020, 7>)
+            .register(Stub::<2020, 9>)
+            .register(Stub::<2020, 12>);
+        registry
+    }
+
+    fn resolve(s: &str) -> Result<Vec<(u32, Vec<Part>)>, String> {
+        s.parse::<Selection>()?.resolve(&registry(), 2020)
+    }
+
+    #[test]
+    fn single_days_and_ranges() {
+        assert_eq!(resolve("7").unwrap(), vec![(7, vec![Part::One, Part::Two])]);
+        assert_eq!(resolve("3-5:1").unwrap(), vec![
+            (3, vec![Part::One]), (4, vec![Part::One]), (5, vec![Part::One]),
+        ]);
+        assert_eq!(resolve(" 9 , 2:2").unwrap(), vec![(2, vec![Part::Two]), (9, vec![Part::One, Part::Two])]);
+    }
+
+    #[test]
+    fn parts_of_a_day_merge() {
+        assert_eq!(resolve("7:2,7:1").unwrap(), vec![(7, vec![Part::One, Part::Two])]);
+        assert_eq!(resolve("1-2:2,2").unwrap(), vec![(1, vec![Part::Two]), (2, vec![Part::One, Part::Two])]);
+    }
+
+    #[test]
+    fn all_and_latest() {
+        let days = resolve("all").unwrap();
+        assert_eq!(days.iter().map(|(day, _)| *day).collect::<Vec<_>>(), [1, 2, 3, 4, 5, 7, 9, 12]);
+        assert!(days.iter().all(|(_, parts)| parts == &[Part::One, Part::Two]));
+        assert_eq!(resolve("latest:1").unwrap(), vec![(12, vec![Part::One])]);
+    }
+
+    #[test]
+    fn invalid_items() {
+        assert_eq!(resolve("5-3").unwrap_err(), "Empty day range '5-3'");
+        assert_eq!(resolve("0").unwrap_err(), "Invalid day '0', expected 1 to 25");
+        assert_eq!(resolve("1-4000000000").unwrap_err(), "Invalid day '4000000000', expected 1 to 25");
+        assert_eq!(resolve("seven").unwrap_err(), "Invalid day 'seven'");
+        assert_eq!(resolve("7:3").unwrap_err(), "Invalid part '3'");
+        assert_eq!(resolve("1,,2").unwrap_err(), "Invalid day ''");
+    }
+
+    #[test]
+    fn unknown_days_and_years() {
+        let error = resolve("24-25").unwrap_err();
+        assert_eq!(error, "Unknown day(s) [24, 25] of 2020, known are [1, 2, 3, 4, 5, 7, 9, 12]");
+        let error = "1".parse::<Selection>().unwrap().resolve(&registry(), 1999).unwrap_err();
+        assert_eq!(error, "There are no solutions for 1999, known years are [2019, 2020]");
+    }
+}
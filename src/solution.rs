@@ -0,0 +1,202 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::config::{day_table, Config, Section};
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+
+/// Solution to a day's puzzle, split into parsing the input and solving each part
+pub trait Solution {
+    /// Year of the advent calendar the puzzle belongs to
+    const YEAR: u32;
+    /// Day of the advent calendar the puzzle belongs to
+    const DAY: u32;
+    /// Model of the puzzle input, shared by both parts
+    type Input: 'static;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    /// Read the puzzle `input` to its model
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError>;
+
+    /// Solve the first part of the puzzle
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1, AocError>;
+
+    /// Solve the second part of the puzzle
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2, AocError>;
+
+    /// Apply the configured `params` of the puzzle, such as the `[y2020.day9]` table
+    ///
+    /// Puzzles without parameters reject any.
+    fn configure(&mut self, params: &Section) -> Result<(), String> {
+        params.expect_keys(&[])
+    }
+
+    /// Commands for exploring the parsed input with `explore`, as usage and description
+    ///
+    /// Puzzles have no commands unless they provide some.
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// Run one of the `commands` with its `arguments` on the parsed `input`, returning its result
+    fn explore(&self, _input: &Self::Input, command: &str, _arguments: &str) -> Result<String, AocError> {
+        Err(AocError::invalid(format!("unknown command '{}'", command)))
+    }
+
+    /// Write a well-formed `random` input of `size` items, such as lines or records, with any known answers
+    ///
+    /// Without a `size`, the input is about as large as a real one.
+    fn generate(&self, _random: &mut Random, _size: Option<usize>) -> Result<Generated, AocError> {
+        Err(AocError::invalid("there is no generator for this day"))
+    }
+}
+
+/// The parts of each day's puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {One, Two}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    /// Read a literal such as `2`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Invalid part '{}'", s)),
+        }
+    }
+}
+
+/// Parsed input of any day, as produced by a `Solver`
+pub type Parsed = Box<dyn Any>;
+
+/// Type erased `Solution` so that days can be stored and run side by side
+pub trait Solver: Send + Sync {
+    fn year(&self) -> u32;
+
+    fn day(&self) -> u32;
+
+    /// Read the puzzle `input` to its (opaque) model
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed, AocError>;
+
+    /// Solve one `part` of the puzzle from input `parse`d by the same solver
+    fn solve(&self, input: &Parsed, part: Part) -> Result<String, AocError>;
+
+    /// Apply the configured `params` of the puzzle
+    fn configure(&mut self, params: &Section) -> Result<(), String>;
+
+    /// Commands for exploring the parsed input, as usage and description
+    fn commands(&self) -> &'static [(&'static str, &'static str)];
+
+    /// Run an exploring `command` with its `arguments` on input `parse`d by the same solver
+    fn explore(&self, input: &Parsed, command: &str, arguments: &str) -> Result<String, AocError>;
+
+    /// Write a random input of `size` items, with any answers known from generating it
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError>;
+}
+
+impl<S: Solution + Send + Sync> Solver for S {
+    fn year(&self) -> u32 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Parsed, AocError> {
+        Ok(Box::new(Solution::parse(self, input)?))
+    }
+
+    fn solve(&self, input: &Parsed, part: Part) -> Result<String, AocError> {
+        let input = input.downcast_ref::<S::Input>().expect("input parsed by another solver");
+        match part {
+            Part::One => self.part1(input).map(|answer| answer.to_string()),
+            Part::Two => self.part2(input).map(|answer| answer.to_string()),
+        }
+    }
+
+    fn configure(&mut self, params: &Section) -> Result<(), String> {
+        Solution::configure(self, params)
+    }
+
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        Solution::commands(self)
+    }
+
+    fn explore(&self, input: &Parsed, command: &str, arguments: &str) -> Result<String, AocError> {
+        let input = input.downcast_ref::<S::Input>().expect("input parsed by another solver");
+        Solution::explore(self, input, command, arguments)
+    }
+
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        Solution::generate(self, random, size)
+    }
+}
+
+/// All known solvers by their year and day
+#[derive(Default)]
+pub struct Registry (BTreeMap<(u32, u32), Box<dyn Solver>>);
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry(BTreeMap::new())
+    }
+
+    /// Add a `solution` for its year and day, replacing any previous one
+    pub fn register<S: Solution + Send + Sync + 'static>(&mut self, solution: S) -> &mut Self {
+        self.0.insert((S::YEAR, S::DAY), Box::new(solution));
+        self
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<&dyn Solver> {
+        self.0.get(&(year, day)).map(|solver| solver.as_ref())
+    }
+
+    /// Apply the `[yYYYY.dayN]` tables of a `config` to the solver of each day
+    ///
+    /// Tables of days without a solver are rejected; other tables are left to the runner.
+    pub fn configure(&mut self, config: &Config) -> Result<(), String> {
+        for name in config.names() {
+            let (year, day) = match day_table(name) {
+                Some(key) => key,
+                None => continue,
+            };
+            match self.0.get_mut(&(year, day)) {
+                Some(solver) => solver.configure(&config.section(name))?,
+                None => return Err(format!(
+                    "There is no solution for day {} of {} to configure with [{}]", day, year, name
+                )),
+            }
+        }
+        Ok(())
+    }
+
+    /// All years with a solver, in order
+    pub fn years(&self) -> Vec<u32> {
+        let mut years: Vec<u32> = self.0.keys().map(|(year, _)| *year).collect();
+        years.dedup();
+        years
+    }
+
+    /// All days of `year` with a solver, in order
+    pub fn days(&self, year: u32) -> impl Iterator<Item=u32> + '_ {
+        self.0.range((year, 0)..=(year, u32::MAX)).map(|((_, day), _)| *day)
+    }
+}
@@ -0,0 +1,347 @@
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{Error, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::cli::{Failure, Options};
+use crate::client::{self, Client};
+use crate::runner::{catch_panics, run_solver};
+use crate::solution::{Part, Registry};
+use crate::utility::load_records;
+
+
+/// What the server said about a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction
+    Wrong,
+    /// Submitted too soon after the previous answer; wait this many seconds
+    Wait(u64),
+    /// The part was already solved, so the answer was not checked
+    AlreadySolved,
+    /// The response was not understood
+    Unknown,
+}
+
+impl Response {
+    /// Read the response from the HTML `page` sent by the server
+    pub fn from_page(page: &str) -> Self {
+        if page.contains("That's the right answer") {
+            Response::Correct
+        } else if page.contains("You gave an answer too recently") {
+            Response::Wait(wait_seconds(page).unwrap_or(60))
+        } else if page.contains("too high") {
+            Response::TooHigh
+        } else if page.contains("too low") {
+            Response::TooLow
+        } else if page.contains("That's not the right answer") {
+            Response::Wrong
+        } else if page.contains("Did you already complete it") {
+            Response::AlreadySolved
+        } else {
+            Response::Unknown
+        }
+    }
+}
+
+/// Seconds to wait according to a `page` containing something like `You have 1m 5s left to wait`
+fn wait_seconds(page: &str) -> Option<u64> {
+    // the page also says "you have to wait" before, so search backwards from the time
+    let end = page.find(" left to wait")?;
+    let start = page[..end].rfind("have ")? + 5;
+    let mut seconds = 0;
+    for field in page[start..end].split_whitespace() {
+        let (number, unit) = field.split_at(field.find(|c: char| !c.is_ascii_digit())?);
+        seconds += number.parse::<u64>().ok()? * match unit {
+            "h" => 3600,
+            "m" => 60,
+            "s" => 1,
+            _ => return None,
+        };
+    }
+    Some(seconds)
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Response::Correct => write!(f, "correct"),
+            Response::TooHigh => write!(f, "too-high"),
+            Response::TooLow => write!(f, "too-low"),
+            Response::Wrong => write!(f, "wrong"),
+            Response::Wait(seconds) => write!(f, "wait-{}", seconds),
+            Response::AlreadySolved => write!(f, "already-solved"),
+            Response::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl FromStr for Response {
+    type Err = String;
+
+    /// Read a literal such as `too-high` or `wait-35`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Response::Correct),
+            "too-high" => Ok(Response::TooHigh),
+            "too-low" => Ok(Response::TooLow),
+            "wrong" => Ok(Response::Wrong),
+            "already-solved" => Ok(Response::AlreadySolved),
+            "unknown" => Ok(Response::Unknown),
+            _ => s.strip_prefix("wait-").and_then(|seconds| seconds.parse().ok()).map(Response::Wait).ok_or_else(
+                || format!("Unknown response '{}'", s)
+            ),
+        }
+    }
+}
+
+/// One answer submitted at some `time`, in seconds since the Unix epoch
+#[derive(Debug, Clone)]
+pub struct Attempt {
+    pub time: u64,
+    pub day: u32,
+    pub part: Part,
+    pub answer: String,
+    pub response: Response,
+}
+
+impl FromStr for Attempt {
+    type Err = String;
+
+    /// Read a literal such as `1607412000 7:2 too-high 6683`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        let (time, key, response, answer) = match fields[..] {
+            [time, key, response, answer] => (time, key, response, answer),
+            _ => return Err(format!("expected an attempt such as '1607412000 7:2 too-high 6683', got '{}'", s)),
+        };
+        let (day, part) = key.split_once(':').ok_or_else(|| format!("Invalid day and part '{}'", key))?;
+        Ok(Attempt {
+            time: time.parse().map_err(|_| format!("Invalid time '{}'", time))?,
+            day: day.parse().map_err(|_| format!("Invalid day '{}'", day))?,
+            part: part.parse()?,
+            answer: String::from(answer),
+            response: response.parse()?,
+        })
+    }
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}:{} {} {}", self.time, self.day, self.part.number(), self.response, self.answer)
+    }
+}
+
+/// Log of all submitted answers, kept in a file
+#[derive(Debug)]
+pub struct Log {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl Log {
+    /// Read the log at `path`, which is empty if the file does not exist yet
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let attempts = match load_records(path, str::parse) {
+            Ok(attempts) => attempts,
+            Err(error) if error.kind() == ErrorKind::NotFound => vec![],
+            Err(error) => return Err(error),
+        };
+        Ok(Log {path: path.to_path_buf(), attempts})
+    }
+
+    /// Reason not to submit `answer` for `part` of `day` at time `now`, if any
+    ///
+    /// This avoids submitting answers known to be wrong, and submitting while rate limited.
+    pub fn objection(&self, day: u32, part: Part, answer: &str, now: u64) -> Option<String> {
+        // the rate limit applies to all days
+        for attempt in &self.attempts {
+            if let Response::Wait(seconds) = attempt.response {
+                if attempt.time + seconds > now {
+                    return Some(format!("rate limited for another {}s", attempt.time + seconds - now))
+                }
+            }
+        }
+        let number = answer.parse::<i128>().ok();
+        for attempt in self.attempts.iter().filter(|attempt| attempt.day == day && attempt.part == part) {
+            let previous = attempt.answer.parse::<i128>().ok();
+            match (&attempt.response, number, previous) {
+                (Response::Correct, _, _) => return Some(format!("already solved with {}", attempt.answer)),
+                (Response::TooHigh, Some(number), Some(previous)) if number >= previous => return Some(format!(
+                    "{} was already too high", attempt.answer
+                )),
+                (Response::TooLow, Some(number), Some(previous)) if number <= previous => return Some(format!(
+                    "{} was already too low", attempt.answer
+                )),
+                (Response::TooHigh, ..) | (Response::TooLow, ..) | (Response::Wrong, ..)
+                if attempt.answer == answer => return Some(format!("{} was already wrong", answer)),
+                _ => (),
+            }
+        }
+        None
+    }
+
+    /// Add an `attempt` to the log and its file
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), Error> {
+        let mut file = OpenOptions::new().append(true).create(true).open(&self.path)?;
+        writeln!(file, "{}", attempt)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+/// Current time in seconds since the Unix epoch
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or(0)
+}
+
+/// Encode `s` for use as a form value
+fn form_encode(s: &str) -> String {
+    s.bytes().map(|byte| match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => String::from(byte as char),
+        _ => format!("%{:02X}", byte),
+    }).collect()
+}
+
+/// Submit the `answer` for `part` of `day` of `year` and read the response of the server
+pub fn submit(client: &Client, year: u32, day: u32, part: Part, answer: &str) -> Result<Response, Error> {
+    let form = format!("level={}&answer={}", part.number(), form_encode(answer));
+    let page = client.request("POST", &format!("/{}/day/{}/answer", year, day), Some(&form))?;
+    Ok(Response::from_page(&String::from_utf8_lossy(&page)))
+}
+
+/// Solve `part` of `day` and submit the answer, unless the log of submissions objects
+///
+/// Fails unless the answer is correct, so that scripts can tell whether the part is solved.
+pub fn run(options: &Options, registry: &Registry, day: u32, part: Part) -> Result<(), Failure> {
+    let result = catch_panics(|| run_solver(registry.get(options.year, day).unwrap(), &[part], &options.input)).map_err(
+        |error| Failure::failed(format!("Day {} failed: {}", day, error))
+    )?;
+    let answer = result.parts[0].answer.clone();
+    let log_path = options.data.join("submissions.txt");
+    let mut log = Log::load(&log_path).map_err(
+        |error| Failure::failed(format!("Cannot read submissions from {}: {}", log_path.display(), error))
+    )?;
+    let now = now();
+    if let Some(objection) = log.objection(day, part, &answer, now) {
+        return Err(Failure::failed(format!(
+            "Not submitting {} for day {} part {}: {}", answer, day, part.number(), objection
+        )))
+    }
+    let response = client::configured(options).and_then(
+        |client| submit(&client, options.year, day, part, &answer)
+    ).map_err(|error| Failure::failed(format!("Cannot submit day {} part {}: {}", day, part.number(), error)))?;
+    println!("Submitted {} for day {} part {}: {}", answer, day, part.number(), response);
+    let correct = response == Response::Correct;
+    log.record(Attempt {time: now, day, part, answer, response}).map_err(
+        |error| Failure::failed(format!("Cannot add to submissions in {}: {}", log_path.display(), error))
+    )?;
+    if correct {Ok(())} else {Err(Failure::reported())}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use super::*;
+    use crate::client::tests::serve_once;
+
+    fn attempt(time: u64, day: u32, part: Part, response: Response, answer: &str) -> Attempt {
+        Attempt {time, day, part, answer: String::from(answer), response}
+    }
+
+    fn log(attempts: Vec<Attempt>) -> Log {
+        Log {path: PathBuf::from("submissions.txt"), attempts}
+    }
+
+    #[test]
+    fn responses() {
+        let page = |text: &str| Response::from_page(&format!("<main><article><p>{}</p></article></main>", text));
+        assert_eq!(page("That's the right answer! You are one gold star closer."), Response::Correct);
+        assert_eq!(page("That's not the right answer; your answer is too high."), Response::TooHigh);
+        assert_eq!(page("That's not the right answer; your answer is too low."), Response::TooLow);
+        assert_eq!(page("That's not the right answer. If you're stuck, ..."), Response::Wrong);
+        let already = "You don't seem to be solving the right level. Did you already complete it?";
+        assert_eq!(page(already), Response::AlreadySolved);
+        assert_eq!(page("Puzzle inputs differ by user."), Response::Unknown);
+    }
+
+    #[test]
+    fn waiting_times() {
+        let page = |left: &str| format!(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  \
+            You have {} left to wait. [Return to Day 1]", left
+        );
+        assert_eq!(Response::from_page(&page("37s")), Response::Wait(37));
+        assert_eq!(wait_seconds(&page("4m 2s")), Some(242));
+        assert_eq!(wait_seconds(&page("1h 0m 1s")), Some(3601));
+        assert_eq!(wait_seconds(&page("a while")), None);
+        // the usual minute is assumed if the time is not understood
+        assert_eq!(Response::from_page(&page("a while")), Response::Wait(60));
+    }
+
+    #[test]
+    fn attempts_round_trip() {
+        for line in &["1607412000 7:2 too-high 6683", "1607412060 7:1 wait-35 12", "1607412000 1:1 correct abc"] {
+            assert_eq!(line.parse::<Attempt>().unwrap().to_string(), *line);
+        }
+        assert!("1607412000 7:2 too-high".parse::<Attempt>().is_err());
+        assert_eq!("1607412000 7:3 wrong 5".parse::<Attempt>().unwrap_err(), "Invalid part '3'");
+        assert_eq!("1607412000 7:2 maybe 5".parse::<Attempt>().unwrap_err(), "Unknown response 'maybe'");
+    }
+
+    #[test]
+    fn objections_to_known_answers() {
+        let log = log(vec![
+            attempt(100, 7, Part::One, Response::TooHigh, "500"),
+            attempt(200, 7, Part::One, Response::TooLow, "100"),
+            attempt(300, 7, Part::One, Response::Wrong, "250"),
+            attempt(400, 1, Part::Two, Response::Correct, "42"),
+        ]);
+        assert_eq!(log.objection(7, Part::One, "600", 1000), Some(String::from("500 was already too high")));
+        assert_eq!(log.objection(7, Part::One, "50", 1000), Some(String::from("100 was already too low")));
+        assert_eq!(log.objection(7, Part::One, "250", 1000), Some(String::from("250 was already wrong")));
+        assert_eq!(log.objection(1, Part::Two, "43", 1000), Some(String::from("already solved with 42")));
+        assert_eq!(log.objection(7, Part::One, "300", 1000), None);
+        // other parts are not affected
+        assert_eq!(log.objection(7, Part::Two, "600", 1000), None);
+    }
+
+    #[test]
+    fn objections_while_rate_limited() {
+        let log = log(vec![attempt(100, 3, Part::One, Response::Wait(60), "7")]);
+        assert_eq!(log.objection(7, Part::Two, "8", 130), Some(String::from("rate limited for another 30s")));
+        assert_eq!(log.objection(7, Part::Two, "8", 160), None);
+    }
+
+    #[test]
+    fn log_file() {
+        let path = env::temp_dir().join(format!("aoc2020_rust_submissions_{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut log = Log::load(&path).unwrap();
+        assert!(log.attempts.is_empty());
+        log.record(attempt(100, 7, Part::Two, Response::TooLow, "6683")).unwrap();
+        log.record(attempt(200, 7, Part::Two, Response::Correct, "6684")).unwrap();
+        let loaded = Log::load(&path).unwrap();
+        assert_eq!(loaded.attempts.iter().map(Attempt::to_string).collect::<Vec<_>>(), vec![
+            "100 7:2 too-low 6683", "200 7:2 correct 6684",
+        ]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn submits_to_server() {
+        let (base_url, server) = serve_once("HTTP/1.0 200 OK\r\n\r\n<p>That's the right answer!</p>");
+        let client = Client::new(&base_url, "secret").unwrap();
+        assert_eq!(submit(&client, 2020, 7, Part::Two, "a b&c").unwrap(), Response::Correct);
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /aoc/2020/day/7/answer HTTP/1.0\r\n"), "{}", request);
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=a%20b%26c"), "{}", request);
+    }
+}
@@ -1,26 +1,56 @@
 use std::fs::File;
-use std::io::{BufRead, Error, BufReader};
+use std::io::{BufRead, BufReader, Error, ErrorKind};
+use std::path::Path;
 use std::str::FromStr;
-use std::fmt::Debug;
+
+use crate::error::AocError;
 
 /// Parse lines from a buffer to a specific type T
-pub fn parse_lines<T: FromStr, R: BufRead>(in_stream: R) -> Result<Vec<T>, Error> where <T as std::str::FromStr>::Err: Debug{
+///
+/// Parse errors are located at the line they occurred in.
+pub fn parse_lines<T: FromStr, R: BufRead>(in_stream: R) -> Result<Vec<T>, AocError> where AocError: From<T::Err> {
     let mut results = vec![];
-    for line in in_stream.lines() {
-        results.push(line?.trim().parse().unwrap())
+    for (index, line) in in_stream.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        results.push(line.parse().map_err(|error| AocError::from(error).at_line(index + 1, line))?)
     }
     Ok(results)
 }
 
-pub fn parse_file_lines<T: FromStr>(path: &str) -> Result<Vec<T>, Error> where <T as std::str::FromStr>::Err: Debug{
-    let f = File::open(&path)?;
-    let values: Vec<T> = parse_lines(BufReader::new(f))?;
-    Ok(values)
+/// Read one record per line from the file at `path`, using `parse` for each line
+///
+/// Empty lines and lines starting with `#` are ignored. Lines `parse` rejects with a reason
+/// are errors which name the file and line.
+pub fn load_records<T, F: FnMut(&str) -> Result<T, String>>(path: &Path, mut parse: F) -> Result<Vec<T>, Error> {
+    let mut records = vec![];
+    for (index, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue
+        }
+        records.push(parse(line).map_err(
+            |reason| Error::new(ErrorKind::InvalidData, format!("{}:{}: {}", path.display(), index + 1, reason))
+        )?);
+    }
+    Ok(records)
 }
 
 pub fn partition(s: &str, by: &str) -> (String, Option<String>) {
-    match s.find(&by) {
+    match s.find(by) {
         Some(i) => (String::from(&s[..i]), Some(String::from(&s[i + by.len()..]))),
         None => (String::from(s), None),
     }
-}
\ No newline at end of file
+}
+
+/// Parse the whitespace separated `arguments` of a command to exactly `count` values of type T
+pub fn parse_arguments<T: FromStr>(arguments: &str, count: usize) -> Result<Vec<T>, AocError> {
+    let arguments: Vec<&str> = arguments.split_whitespace().collect();
+    if arguments.len() != count {
+        return Err(AocError::invalid(format!("expected {} argument(s), got {}", count, arguments.len())))
+    }
+    arguments.iter().map(
+        |argument| argument.parse().map_err(|_| AocError::invalid(format!("invalid argument '{}'", argument)))
+    ).collect()
+}
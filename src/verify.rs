@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::io::Error;
+use std::path::Path;
+
+use crate::runner::DayResult;
+use crate::solution::Part;
+use crate::utility::load_records;
+
+
+/// Expected answer of each known day and part
+#[derive(Debug, Default)]
+pub struct Answers(BTreeMap<(u32, Part), String>);
+
+impl Answers {
+    /// Read answers from a file with lines such as `7:2 6683`
+    ///
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let answers = load_records(path, |line| {
+            let key_answer = line.find(' ').and_then(|i| {
+                let (key, answer) = (&line[..i], line[i + 1..].trim());
+                let colon = key.find(':')?;
+                Some(((key[..colon].parse().ok()?, key[colon + 1..].parse().ok()?), String::from(answer)))
+            });
+            key_answer.ok_or_else(|| format!("expected an answer such as '7:2 6683', got '{}'", line))
+        })?;
+        Ok(Answers(answers.into_iter().collect()))
+    }
+
+    pub fn expected(&self, day: u32, part: Part) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+}
+
+/// Outcome of comparing an answer to its expected answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {expected: String, actual: String},
+    /// There is no expected answer to compare to
+    Missing {actual: String},
+}
+
+/// Compare the answers of a `result` to the expected `answers`
+pub fn verify(result: &DayResult, answers: &Answers) -> Vec<(Part, Verdict)> {
+    result.parts.iter().map(|part| {
+        let verdict = match (answers.expected(result.day, part.part), &part.answer) {
+            (Some(expected), actual) if expected == actual => Verdict::Pass,
+            (Some(expected), actual) => Verdict::Fail {
+                expected: String::from(expected), actual: actual.clone(),
+            },
+            (None, actual) => Verdict::Missing {actual: actual.clone()},
+        };
+        (part.part, verdict)
+    }).collect()
+}
+
+/// Print the verdict for each part of some `results` and a summary
+///
+/// Returns whether no answer failed; missing expected answers are not failures.
+pub fn print_verification(results: &[DayResult], answers: &Answers) -> bool {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for result in results {
+        for (part, verdict) in verify(result, answers) {
+            print!("Day {:>2} Part {}: ", result.day, part.number());
+            match verdict {
+                Verdict::Pass => {
+                    passed += 1;
+                    println!("pass");
+                },
+                Verdict::Fail {expected, actual} => {
+                    failed += 1;
+                    println!("FAIL expected {}, got {}", expected, actual);
+                },
+                Verdict::Missing {actual} => {
+                    missing += 1;
+                    println!("missing expected answer, got {}", actual);
+                },
+            }
+        }
+    }
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    failed == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::runner::PartResult;
+
+    fn result(day: u32, answers: &[(Part, &str)]) -> DayResult {
+        let parts = answers.iter().map(|(part, answer)| PartResult {
+            part: *part, answer: String::from(*answer), elapsed: Duration::ZERO, alloc: None,
+        }).collect();
+        DayResult {day, parse: Duration::ZERO, parts, runs: 1, parse_alloc: None, alloc: None}
+    }
+
+    fn answers(content: &str, name: &str) -> Result<Answers, Error> {
+        let path = env::temp_dir().join(format!("aoc2020_rust_answers_{}_{}.txt", name, std::process::id()));
+        fs::write(&path, content).unwrap();
+        let answers = Answers::load(&path);
+        fs::remove_file(&path).unwrap();
+        answers
+    }
+
+    #[test]
+    fn verdicts() {
+        let answers = answers("# day:part answer\n\n7:1 172\n7:2  6683 \n9:1 abc def\n", "verdicts").unwrap();
+        assert_eq!(answers.expected(7, Part::Two), Some("6683"));
+        assert_eq!(answers.expected(9, Part::One), Some("abc def"));
+        assert_eq!(verify(&result(7, &[(Part::One, "172"), (Part::Two, "6684")]), &answers), vec![
+            (Part::One, Verdict::Pass),
+            (Part::Two, Verdict::Fail {expected: String::from("6683"), actual: String::from("6684")}),
+        ]);
+        assert_eq!(verify(&result(9, &[(Part::Two, "12")]), &answers), vec![
+            (Part::Two, Verdict::Missing {actual: String::from("12")}),
+        ]);
+    }
+
+    #[test]
+    fn malformed_answers() {
+        for (line, content) in [("7:2", "7:1 172\n7:2\n"), ("7-2 6683", "7-2 6683\n"), ("7:3 6683", "\n7:3 6683\n")] {
+            let error = answers(content, "malformed").unwrap_err().to_string();
+            let reason = format!("expected an answer such as '7:2 6683', got '{}'", line);
+            assert!(error.ends_with(&reason), "{}", error);
+        }
+    }
+}
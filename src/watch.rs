@@ -0,0 +1,88 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::cli::{Command, Failure, Options};
+use crate::runner::{run_solver, with_timeout, catch_panics, print_result, InputSource};
+use crate::solution::{Part, Registry, Solver};
+
+
+/// Time between checks of the watched files
+const POLL: Duration = Duration::from_millis(250);
+/// Time the watched files must stay unchanged before running again
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Modification time of a file, if it exists
+fn modified(path: &PathBuf) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Run the `parts` of a `solver` on an input `source` and show the results
+///
+/// Panics and runs exceeding the `timeout` are reported like any other failure, so that watching goes on.
+fn run_input(solver: &'static dyn Solver, parts: &[Part], source: &InputSource, timeout: Option<Duration>) {
+    println!("[> Input {} <]", source.name(solver.day()));
+    let (parts, input) = (parts.to_vec(), source.clone());
+    match with_timeout(timeout, move || catch_panics(|| run_solver(solver, &parts, &input))) {
+        Ok(result) => print_result(&result),
+        Err(error) => eprintln!("Day {} failed: {}", solver.day(), error),
+    }
+}
+
+/// Run the `parts` of a `solver` on its input and on the `example` input, if it has any content
+fn run_inputs(
+    solver: &'static dyn Solver, parts: &[Part], source: &InputSource, example: &Path, timeout: Option<Duration>
+) {
+    // new days start with an empty example, which is not worth reporting as failing
+    if fs::metadata(example).map(|metadata| metadata.len() > 0).unwrap_or(false) {
+        run_input(solver, parts, &InputSource::File(example.to_path_buf()), timeout);
+    }
+    run_input(solver, parts, source, timeout);
+}
+
+/// Run the `parts` of a `solver` whenever its input `source` or example input in `data` changes
+///
+/// Changes are only picked up once the files have been unchanged for a short while,
+/// so that a burst of saves leads to a single run. Each run gives up after the `timeout`, if any.
+/// This never returns.
+pub fn watch(
+    solver: &'static dyn Solver, parts: &[Part], source: &InputSource, data: &Path, timeout: Option<Duration>
+) -> ! {
+    let example = InputSource::example_path(data, solver.day());
+    let mut paths = vec![example.clone()];
+    paths.extend(source.path(solver.day()));
+    let snapshot = || paths.iter().map(modified).collect::<Vec<_>>();
+    let mut seen = snapshot();
+    run_inputs(solver, parts, source, &example, timeout);
+    loop {
+        thread::sleep(POLL);
+        let mut current = snapshot();
+        if current == seen {
+            continue
+        }
+        loop {
+            thread::sleep(DEBOUNCE);
+            let settled = snapshot();
+            if settled == current {
+                break
+            }
+            current = settled;
+        }
+        seen = current;
+        println!();
+        run_inputs(solver, parts, source, &example, timeout);
+    }
+}
+
+/// Watch the single `selected` day, which never returns unless the `options` do not allow watching
+pub fn run(options: &Options, registry: &'static Registry, selected: &[(u32, Vec<Part>)]) -> Result<(), Failure> {
+    if selected.len() != 1 || options.command != Command::Run || options.bench.is_some() {
+        return Err(Failure::usage("Option '--watch' requires running a single day"))
+    }
+    if matches!(options.input, InputSource::Stdin) {
+        return Err(Failure::usage("Option '--watch' cannot watch stdin"))
+    }
+    let (day, parts) = &selected[0];
+    watch(registry.get(options.year, *day).unwrap(), parts, &options.input, &options.data, options.timeout)
+}
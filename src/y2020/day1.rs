@@ -0,0 +1,137 @@
+use std::convert::TryFrom;
+use std::io::BufRead;
+
+use crate::config::Section;
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Part, Solution};
+use crate::utility::{parse_arguments, parse_lines};
+use crate::verbose;
+
+pub struct Puzzle {
+    /// Sum the entries must add up to
+    pub target: i32,
+}
+
+impl Default for Puzzle {
+    fn default() -> Self {
+        Puzzle {target: 2020}
+    }
+}
+
+impl Solution for Puzzle {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 1;
+    type Input = Vec<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse_lines(input)
+    }
+
+    fn part1(&self, values: &Self::Input) -> Result<i32, AocError> {
+        search_double(values, self.target).map(|(a, b)| {
+            verbose!("Day 1 found {} + {} = {}", a, b, self.target);
+            a * b
+        }).ok_or_else(
+            || AocError::invalid(format!("no two entries sum to {}", self.target))
+        )
+    }
+
+    fn part2(&self, values: &Self::Input) -> Result<i32, AocError> {
+        search_triple(values, self.target).map(|(a, b, c)| {
+            verbose!("Day 1 found {} + {} + {} = {}", a, b, c, self.target);
+            a * b * c
+        }).ok_or_else(
+            || AocError::invalid(format!("no three entries sum to {}", self.target))
+        )
+    }
+
+    fn configure(&mut self, params: &Section) -> Result<(), String> {
+        params.expect_keys(&["target"])?;
+        if let Some(target) = params.integer("target")? {
+            self.target = i32::try_from(target).map_err(|_| format!("Key 'target' in [{}] is too large", params.name()))?;
+        }
+        Ok(())
+    }
+
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("pair TOTAL", "two entries that add up to TOTAL and their product"),
+            ("triple TOTAL", "three entries that add up to TOTAL and their product"),
+        ]
+    }
+
+    fn explore(&self, values: &Self::Input, command: &str, arguments: &str) -> Result<String, AocError> {
+        let total = parse_arguments(arguments, 1)?[0];
+        match command {
+            "pair" => search_double(values, total).map(|(a, b)| format!("{} * {} = {}", a, b, a * b)),
+            "triple" => search_triple(values, total).map(|(a, b, c)| format!("{} * {} * {} = {}", a, b, c, a * b * c)),
+            _ => return Err(AocError::invalid(format!("unknown command '{}'", command))),
+        }.ok_or_else(|| AocError::invalid(format!("no entries sum to {}", total)))
+    }
+
+    /// Entries with exactly one pair and one triple adding up to the target, 200 by default
+    ///
+    /// All other entries are larger than the target, so they cannot be part of any sum.
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        let (size, target) = (size.unwrap_or(200), self.target);
+        if size < 5 || target < 9 {
+            return Err(AocError::invalid("need at least 5 entries and a target of at least 9"))
+        }
+        let pick = |random: &mut Random, below: i32| random.between(1, below as u64 - 1) as i32;
+        for _ in 0..1000 {
+            let a = pick(random, target);
+            let c = pick(random, target - 1);
+            let d = pick(random, target - c);
+            let planted = [a, target - a, c, d, target - c - d];
+            // sums may repeat an entry, so a pair or triple must be the only one of its kind
+            let (mut pairs, mut triples) = (vec![], vec![]);
+            for x in planted.iter() {
+                for y in planted.iter() {
+                    if x + y == target {
+                        pairs.push(x * y);
+                    }
+                    triples.extend(planted.iter().filter(|z| x + y + *z == target).map(|z| x * y * z));
+                }
+            }
+            let single = |products: &[i32]| products.iter().all(|product| *product == products[0]);
+            if !single(&pairs) || !single(&triples) {
+                continue
+            }
+            let mut entries = planted.to_vec();
+            entries.extend((5..size).map(|_| random.between(target as u64 + 1, 2 * target as u64) as i32));
+            random.shuffle(&mut entries);
+            let input = entries.iter().map(|entry| format!("{}\n", entry)).collect();
+            return Ok(Generated::new(input).plant(Part::One, pairs[0]).plant(Part::Two, triples[0]))
+        }
+        Err(AocError::invalid(format!("found no entries with a single pair and triple adding up to {}", target)))
+    }
+}
+
+/// Find two `candidates` that add up to `total`
+pub fn search_double(candidates: &[i32], total: i32) -> Option<(&i32, &i32)> {
+    for a in candidates {
+        for b in candidates {
+            if a + b == total {
+                return Some((a, b));
+            }
+        }
+    }
+    None
+}
+
+/// Find three `candidates` that add up to `total`
+pub fn search_triple(candidates: &[i32], total: i32) -> Option<(&i32, &i32, &i32)> {
+    for a in candidates {
+        for b in candidates {
+            for c in candidates {
+                if a + b + c == total {
+                    return Some((a, b, c));
+                }
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,113 @@
+use std::io::BufRead;
+use std::iter::once;
+
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Part, Solution};
+use crate::utility::parse_lines;
+use crate::verbose;
+
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 10;
+    /// Steps between the sorted connectors, from outlet to device
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        Ok(shortest_steps(parse_lines(input)?))
+    }
+
+    /// Number of 1-steps multiplied by number of 3-steps
+    fn part1(&self, steps: &Self::Input) -> Result<i64, AocError> {
+        let step_counts = count(steps);
+        Ok(step_counts.0 * step_counts.1)
+    }
+
+    /// Number of distinct connector arrangements
+    fn part2(&self, steps: &Self::Input) -> Result<i64, AocError> {
+        Ok(variations(steps))
+    }
+
+    /// Connectors in runs of up to four 1-steps between 3-steps, 100 connectors by default
+    ///
+    /// Runs are shortened once the number of arrangements would no longer fit into 64 bits.
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        // arrangements of a run of 1-steps by its length
+        const WAYS: [i64; 5] = [1, 1, 2, 4, 7];
+        let size = size.unwrap_or(100);
+        let (mut steps, mut arrangements) = (vec![], 1i64);
+        while steps.len() < size {
+            let mut length = (random.between(0, 4) as usize).min(size - steps.len());
+            if arrangements.checked_mul(WAYS[length]).is_none() {
+                length = length.min(1);
+            }
+            arrangements *= WAYS[length];
+            steps.extend((0..length).map(|_| 1));
+            if steps.len() < size {
+                steps.push(3);
+            }
+        }
+        let ones = steps.iter().filter(|&&step| step == 1).count();
+        // the device is always a 3-step above the largest connector
+        let threes = steps.len() - ones + 1;
+        let mut connectors: Vec<i64> = steps.iter().scan(0, |joltage, step| {
+            *joltage += step;
+            Some(*joltage)
+        }).collect();
+        random.shuffle(&mut connectors);
+        let input = connectors.iter().map(|connector| format!("{}\n", connector)).collect();
+        Ok(Generated::new(input).plant(Part::One, ones * threes).plant(Part::Two, arrangements))
+    }
+}
+
+/// Steps between the sorted `connectors`, including outlet and device
+pub fn shortest_steps(mut connectors: Vec<i64>) -> Vec<i64> {
+    connectors.sort_unstable();
+    // precede total-0 for the outlet, append step-3 for the device
+    once(&0).chain(&connectors).zip(&connectors).map(
+        |(prev, next)| next - prev
+    ).chain(once(3)).collect()
+}
+
+/// Number of 1-steps and 3-steps
+pub fn count(steps: &[i64]) -> (i64, i64) {
+    steps.iter().fold(
+        (0, 0), |acc, step| match step {
+            1 => (acc.0 + 1, acc.1),
+            3 => (acc.0, acc.1 + 1),
+            x => {verbose!("Day 10 discards step {}", x); acc},
+        }
+    )
+}
+
+// # To find the number of variations, it is enough to look at the steps instead of the values:
+// If we have the steps 3111, we can shorten it to 33, 321, 312 without caring about the values.
+// Notably, we only need to look at sequences of 1s and 2s; sequences of 3s can never be shortened.
+// Conveniently, there are no 2s in the data. It is enough to know the sequences of 1s and to count
+// their variations.
+// 11 +> 2
+// 111 +> 3, 12, 21
+// 1111 +> 31, 211, 22, 121, 112, 13
+// 11111 +> 311, 131, 113, 32, 23, 2111, 1211, 1121, 1112, 221, 212, 122
+// Given that we can only replace 11 and 111, we can work from one end and consume the
+// variations of the remainder.
+pub fn variations(steps: &[i64]) -> i64 {
+    // variations for a given length. cost[3] => Cost of 111
+    let mut cache = vec![1, 1, 2, 4];
+    // positions of 3-steps
+    let starts: Vec<_> = steps.iter().enumerate().filter(
+        |(_, &delta)| delta == 3
+    ).map(|(i, _)| i + 1).collect();
+    let lengths: Vec<_> = once(&0).chain(&starts).zip(&starts).map(
+        |(prev_i, curr_i)| curr_i - prev_i - 1
+    ).collect();
+    for length in cache.len()..*lengths.iter().max().unwrap() + 1 {
+        cache.push(cache[length-1] + cache[length-2] + cache[length-3]);
+    }
+    lengths.iter().map(|&length| cache[length]).product()
+}
@@ -1,24 +1,63 @@
-use std::io::Error;
-use std::iter::{repeat, once};
+use std::io::BufRead;
+use std::iter::once;
 
-use crate::utility::parse_file_lines;
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Part, Solution};
+use crate::utility::parse_lines;
+use crate::{trace, verbose};
 
 
-pub fn solve() -> Result<(), Error> {
-    let rows: Vec<String> = parse_file_lines("data/day11.txt")?;
-    let seats = Seats::from_rows(rows);
-    let stable_seats = seats.clone().stable_neighbours(false);
-    println!("Neighbour count {}", stable_seats.occupied());
-    let stable_seats = seats.stable_neighbours(true);
-    println!("Visible count {}", stable_seats.occupied());
-    Ok(())
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 11;
+    type Input = Seats;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        Seats::from_rows(parse_lines(input)?)
+    }
+
+    /// Occupied seats once stable with the neighbour rules
+    fn part1(&self, seats: &Self::Input) -> Result<usize, AocError> {
+        Ok(seats.clone().stable_neighbours(false).occupied())
+    }
+
+    /// Occupied seats once stable with the visibility rules
+    fn part2(&self, seats: &Self::Input) -> Result<usize, AocError> {
+        Ok(seats.clone().stable_neighbours(true).occupied())
+    }
+
+    /// Square seating area with about three seats in four positions, 90 rows by default
+    ///
+    /// Not every random layout becomes stable, so layouts are simulated with both rules
+    /// and discarded unless both settle within a few hundred generations.
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        let size = size.unwrap_or(90);
+        for _ in 0..100 {
+            let rows: Vec<String> = (0..size).map(
+                |_| (0..size).map(|_| if random.chance(75) {'L'} else {'.'}).collect()
+            ).collect();
+            let seats = Seats::from_rows(rows.clone())?;
+            let answers = seats.clone().settle(false, 500).zip(seats.settle(true, 500));
+            if let Some((neighbours, visible)) = answers {
+                let input = rows.iter().map(|row| format!("{}\n", row)).collect();
+                return Ok(Generated::new(input).plant(Part::One, neighbours.occupied())
+                    .plant(Part::Two, visible.occupied()))
+            }
+        }
+        Err(AocError::invalid("found no layout that becomes stable, try another seed"))
+    }
 }
 
 /// 2D Vector of seats
 /// This is padded on all directions, with a row/column of empty positions
 #[derive(PartialEq)]
 #[derive(Clone)]
-struct Seats{
+pub struct Seats{
     /// Whether a seat is at row/column
     positions: Vec<Vec<bool>>,
     /// Whether some is at row/column
@@ -29,13 +68,13 @@ struct Seats{
 
 impl Seats {
     /// Compute the visibility of seats for each seat
-    fn _visibility(positions: &Vec<Vec<bool>>) -> Vec<Vec<[(usize, usize); 8]>> {
+    fn _visibility(positions: &[Vec<bool>]) -> Vec<Vec<[(usize, usize); 8]>> {
         let mut visibility = vec![vec![[(0usize, 0usize);8]; positions[0].len()]; positions.len()];
         for (row_i, row) in positions.iter().enumerate() {
             for (column_i, seat) in row.iter().enumerate() {
                 if *seat {
                     visibility[row_i][column_i] = Seats::_seat_visiblity(
-                        &positions, row_i, column_i
+                        positions, row_i, column_i
                     );
                 }
             }
@@ -43,7 +82,8 @@ impl Seats {
         visibility
     }
 
-    fn _seat_visiblity(positions: &Vec<Vec<bool>>, row_i: usize, column_i: usize) -> [(usize, usize); 8] {
+    #[allow(clippy::needless_range_loop)]
+    fn _seat_visiblity(positions: &[Vec<bool>], row_i: usize, column_i: usize) -> [(usize, usize); 8] {
         let (max_row, max_column) = (positions.len() - 1, positions[0].len() - 1);
         // up left, up, up right, right, down right, down, down left, left
         // 0:0 is always empty, like any other border field
@@ -99,14 +139,26 @@ impl Seats {
         visibility
     }
 
-    fn from_rows(rows: Vec<String>) -> Self {
-        let (num_rows, num_colums) = (rows.len(), rows[0].len());
-        let occupancy: Vec<Vec<bool>> = repeat(
-                repeat(false).take(num_colums + 2).collect()
-            ).take(num_rows + 2).collect();
+    /// Read the layout from `rows` such as `L.LL.LL.LL`
+    pub fn from_rows(rows: Vec<String>) -> Result<Self, AocError> {
+        let (num_rows, num_colums) = match rows.first() {
+            Some(row) if !row.is_empty() => (rows.len(), row.len()),
+            _ => return Err(AocError::invalid("there are no seats")),
+        };
+        for (index, row) in rows.iter().enumerate() {
+            let error = match row.chars().position(|c| !"L.".contains(c)) {
+                Some(column) => AocError::parse(row, column + 1, "expected an empty seat 'L' or floor '.'"),
+                None if row.len() != num_colums => AocError::parse(
+                    row, 1, format!("expected {} positions like the first row", num_colums)
+                ),
+                None => continue,
+            };
+            return Err(error.at_line(index + 1, row))
+        }
+        let occupancy: Vec<Vec<bool>> = vec![vec![false; num_colums + 2]; num_rows + 2];
         let mut positions = Vec::new();
         // there are no seats at the padding; add it anyway for consistency
-        positions.push(repeat(false).take(num_colums + 2).collect());
+        positions.push(vec![false; num_colums + 2]);
         for row in rows {
             positions.push(
                 once(false).chain(row.chars().map(
@@ -114,28 +166,36 @@ impl Seats {
                 ).chain(once(false))).collect()
             )
         };
-        positions.push(repeat(false).take(num_colums + 2).collect());
+        positions.push(vec![false; num_colums + 2]);
         let visibility= Seats::_visibility(&positions);
-        Self {positions, occupancy, visibility}
+        Ok(Self {positions, occupancy, visibility})
     }
 
     /// Evolve seats until the layout is stable
-    fn stable_neighbours(self, visible: bool) -> Self {
+    pub fn stable_neighbours(self, visible: bool) -> Self {
+        self.settle(visible, usize::MAX).expect("seats evolve until they are stable without a limit")
+    }
+
+    /// Evolve seats until the layout is stable, or `None` if it is not within `limit` generations
+    pub fn settle(self, visible: bool, limit: usize) -> Option<Self> {
         let mut prev = self.occupancy.clone();
         let mut next = self.evolve_neighbours(visible);
-        while next.occupancy != prev {
+        for generation in 1..limit {
+            if next.occupancy == prev {
+                verbose!("Day 11 is stable after {} generations with {} occupied seats", generation, next.occupied());
+                return Some(next)
+            }
+            trace!("Day 11 generation {} has {} occupied seats", generation, next.occupied());
             prev = next.occupancy.clone();
             next = next.evolve_neighbours(visible);
         }
-        next
+        None
     }
 
     /// Compute the next layout using neighbor or visible rules
-    fn evolve_neighbours(self, visible: bool) -> Self {
+    pub fn evolve_neighbours(self, visible: bool) -> Self {
         let threshold = if visible {5} else {4};
-        let mut new_occupancy: Vec<Vec<bool>> = repeat(
-            vec![false; self.positions[0].len()]
-        ).take(self.occupancy.len()).collect();
+        let mut new_occupancy: Vec<Vec<bool>> = vec![vec![false; self.positions[0].len()]; self.occupancy.len()];
         for (row_i, row) in self.positions.iter().enumerate() {
             for (column_i, seat) in row.iter().enumerate() {
                 if *seat {
@@ -163,7 +223,7 @@ impl Seats {
     }
 
     /// Number of occupied seats next to the one at `row_i`, `column_i`
-    fn neighbours(&self, row_i: usize, column_i: usize) -> usize {
+    pub fn neighbours(&self, row_i: usize, column_i: usize) -> usize {
         let occupancy = &self.occupancy;
         // NB: Iterating over offsets might be better, but I got fed up with signed - usize math :/
         [
@@ -179,14 +239,14 @@ impl Seats {
     }
 
     /// Number of occupied seats visible from the one at `row_i`, `column_i`
-    fn visible(&self, row_i: usize, column_i: usize) -> usize {
+    pub fn visible(&self, row_i: usize, column_i: usize) -> usize {
         self.visibility[row_i][column_i].iter().map(
             |(row, col)| self.occupancy[*row][*col]
         ).filter(|&b| b).count()
     }
 
     /// Number of occupied seats in total
-    fn occupied(&self) -> usize {
+    pub fn occupied(&self) -> usize {
         self.occupancy.iter().flatten().filter(|&seat| *seat).count()
     }
 }
\ No newline at end of file
@@ -0,0 +1,219 @@
+use std::str::FromStr;
+use std::ops::{Add, Mul, AddAssign};
+use std::io::BufRead;
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Part, Solution};
+use crate::utility::parse_lines;
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 12;
+    type Input = Vec<Instruction>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse_lines(input)
+    }
+
+    /// Distance travelled when directions move the ship
+    fn part1(&self, directions: &Self::Input) -> Result<i32, AocError> {
+        Ok(navigate(directions)?.manhattan())
+    }
+
+    /// Distance travelled when directions move the waypoint
+    fn part2(&self, directions: &Self::Input) -> Result<i32, AocError> {
+        Ok(direct(directions)?.manhattan())
+    }
+
+    /// Random instructions, 780 by default, mostly moving forward
+    ///
+    /// Both ways of navigating are followed while generating, in 64 bits to notice leaving the 32 bits of the solvers.
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        let rotate = |(x, y): (i64, i64), quarters: i64| (0..quarters).fold((x, y), |(x, y), _| (-y, x));
+        let (mut ship, mut heading) = ((0, 0), (1, 0));
+        let (mut position, mut waypoint) = ((0, 0), (10, 1));
+        let mut input = String::new();
+        for _ in 0..size.unwrap_or(780) {
+            let action = *random.choose(&['N', 'S', 'E', 'W', 'L', 'R', 'F', 'F']);
+            let amount = match action {
+                'L' | 'R' => *random.choose(&[90, 180, 270]),
+                _ => random.between(1, 100) as i64,
+            };
+            let (dx, dy) = match action {
+                'N' => (0, amount),
+                'S' => (0, -amount),
+                'E' => (amount, 0),
+                'W' => (-amount, 0),
+                _ => (0, 0),
+            };
+            ship = (ship.0 + dx, ship.1 + dy);
+            waypoint = (waypoint.0 + dx, waypoint.1 + dy);
+            match action {
+                'F' => {
+                    ship = (ship.0 + heading.0 * amount, ship.1 + heading.1 * amount);
+                    position = (position.0 + waypoint.0 * amount, position.1 + waypoint.1 * amount);
+                },
+                'L' | 'R' => {
+                    // turning right is turning left three times as often
+                    let quarters = if action == 'L' {amount / 90} else {4 - amount / 90};
+                    heading = rotate(heading, quarters);
+                    waypoint = rotate(waypoint, quarters);
+                },
+                _ => (),
+            }
+            // the solvers also multiply the waypoint by up to 100 when moving forward
+            let distances = [ship, position, (waypoint.0 * 100, waypoint.1 * 100)].map(|(x, y)| x.abs() + y.abs());
+            if distances.iter().any(|&distance| distance > i32::MAX as i64) {
+                return Err(AocError::invalid("the ship sails beyond 32 bits, use fewer instructions"))
+            }
+            input += &format!("{}{}\n", action, amount);
+        }
+        Ok(Generated::new(input).plant(Part::One, ship.0.abs() + ship.1.abs())
+            .plant(Part::Two, position.0.abs() + position.1.abs()))
+    }
+}
+
+#[derive(Debug)]
+pub struct Instruction(pub char, pub i32);
+
+impl FromStr for Instruction {
+    type Err = AocError;
+
+    /// Read a literal such as `F10`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let identifier = match s.chars().next() {
+            Some(c) if "NSEWFLR".contains(c) => c,
+            Some(c) => return Err(AocError::parse(s, 1, format!("unknown instruction '{}'", c))),
+            None => return Err(AocError::parse(s, 1, "expected an instruction such as 'F10'")),
+        };
+        let number = &s[identifier.len_utf8()..];
+        match number.parse() {
+            Ok(amount) => Ok(Self (identifier, amount)),
+            Err(_) => Err(AocError::parse(s, 2, format!("invalid amount '{}'", number))),
+        }
+    }
+}
+
+/// 2D Vector, with x pointing right and y pointing up
+#[derive(Debug)]
+pub struct Vector2{
+    pub x: i32,
+    pub y: i32,
+}
+
+#[allow(clippy::upper_case_acronyms)]
+pub enum Rotation {LEFT, RIGHT, FLIP}
+
+impl Vector2 {
+    pub const fn right() -> Self { Self {x: 1, y: 0} }
+    pub const fn left() -> Self { Self {x: -1, y: 0} }
+    pub const fn up() -> Self { Self {x: 0, y: 1} }
+    pub const fn dow() -> Self { Self {x: 0, y: -1} }
+
+    pub const fn zero() -> Self { Self {x: 0, y: 0} }
+
+    pub fn manhattan(&self) -> i32 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn rotate(&self, by: &Rotation) -> Self {
+        match by {
+            Rotation::LEFT => Self {x: -self.y, y: self.x},
+            Rotation::RIGHT => Self {x: self.y, y: -self.x},
+            Rotation::FLIP => Self {x: -self.x, y: -self.y},
+        }
+    }
+}
+
+impl Add for &Vector2 {
+    type Output = Vector2;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self::Output {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl AddAssign for Vector2 {
+    fn add_assign(&mut self, other: Self) {
+        *self = Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        };
+    }
+}
+
+impl Mul<&i32> for &Vector2 {
+    type Output = Vector2;
+
+    fn mul(self, other: &i32) -> Self::Output {
+        Self::Output {
+            x: self.x * other,
+            y: self.y * other,
+        }
+    }
+}
+
+/// Destination when `directions` move the ship
+pub fn navigate(directions: &[Instruction]) -> Result<Vector2, AocError> {
+    let (mut position, mut orientation) = (Vector2::zero(), Vector2::right());
+    for instruction in directions {
+        match instruction {
+            Instruction('N', amount) => position += &Vector2::up() * amount,
+            Instruction('S', amount) => position += &Vector2::dow() * amount,
+            Instruction('E', amount) => position += &Vector2::right() * amount,
+            Instruction('W', amount) => position += &Vector2::left() * amount,
+            Instruction('F', amount) => position += &orientation * amount,
+            Instruction('R', amount) => match amount % 360 {
+                90 => orientation = orientation.rotate(&Rotation::RIGHT),
+                180 => orientation = orientation.rotate(&Rotation::FLIP),
+                270 => orientation = orientation.rotate(&Rotation::LEFT),
+                _ => (),
+            },
+            Instruction('L', amount) => match amount % 360 {
+                90 => orientation = orientation.rotate(&Rotation::LEFT),
+                180 => orientation = orientation.rotate(&Rotation::FLIP),
+                270 => orientation = orientation.rotate(&Rotation::RIGHT),
+                _ => (),
+            },
+            smt => return Err(AocError::invalid(format!("Unknown instruction {:?}", smt))),
+        }
+    }
+    Ok(position)
+}
+
+
+/// Destination when `directions` move the waypoint
+pub fn direct(directions: &[Instruction]) -> Result<Vector2, AocError> {
+    let mut position = Vector2::zero();
+    let mut waypoint = &Vector2::up() + &(&Vector2::right() * &10);
+    for instruction in directions {
+        match instruction {
+            Instruction('N', amount) => waypoint += &Vector2::up() * amount,
+            Instruction('S', amount) => waypoint += &Vector2::dow() * amount,
+            Instruction('E', amount) => waypoint += &Vector2::right() * amount,
+            Instruction('W', amount) => waypoint += &Vector2::left() * amount,
+            Instruction('F', amount) => position += &waypoint * amount,
+            Instruction('R', amount) => match amount % 360 {
+                90 => waypoint = waypoint.rotate(&Rotation::RIGHT),
+                180 => waypoint = waypoint.rotate(&Rotation::FLIP),
+                270 => waypoint = waypoint.rotate(&Rotation::LEFT),
+                _ => (),
+            },
+            Instruction('L', amount) => match amount % 360 {
+                90 => waypoint = waypoint.rotate(&Rotation::LEFT),
+                180 => waypoint = waypoint.rotate(&Rotation::FLIP),
+                270 => waypoint = waypoint.rotate(&Rotation::RIGHT),
+                _ => (),
+            },
+            smt => return Err(AocError::invalid(format!("Unknown instruction {:?}", smt))),
+        }
+    }
+    Ok(position)
+}
@@ -0,0 +1,160 @@
+use std::io::BufRead;
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Part, Solution};
+use crate::utility::parse_lines;
+use crate::verbose;
+
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 13;
+    type Input = Timetable;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse_timetable(&parse_lines(input)?)
+    }
+
+    /// Id of the earliest bus multiplied by the time waiting for it
+    fn part1(&self, timetable: &Self::Input) -> Result<u64, AocError> {
+        let bus_ids = timetable.buses.iter().map(|bus| bus.number).collect();
+        let (earliest_wait, earliest_id) = find_bus(timetable.departure, bus_ids).ok_or_else(
+            || AocError::invalid("there are no buses")
+        )?;
+        Ok(earliest_id * earliest_wait)
+    }
+
+    /// Earliest time at which all buses depart at their offset
+    fn part2(&self, timetable: &Self::Input) -> Result<u64, AocError> {
+        Ok(common_time(&timetable.buses))
+    }
+
+    /// Timetable of as many buses as given, 9 by default, with numbers that are distinct primes
+    ///
+    /// Like the real timetables, two buses have numbers above 400 and the others below 60. The common
+    /// time is derived with modular inverses instead of the search of the solver.
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        let size = size.unwrap_or(9);
+        let primes = (11..1000).filter(|&n| (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0));
+        let (mut small, mut large): (Vec<u64>, Vec<u64>) = primes.partition(|&prime| prime < 60);
+        large.retain(|&prime| prime > 400);
+        random.shuffle(&mut small);
+        random.shuffle(&mut large);
+        let mut buses: Vec<u64> = large.iter().take(2).chain(&small).take(size).copied().collect();
+        let product = buses.iter().try_fold(1u64, |product, &bus| product.checked_mul(bus).filter(|&p| p < 1 << 63));
+        if buses.len() < size || product.is_none() {
+            return Err(AocError::invalid("the common time of so many buses exceeds 63 bits, use fewer buses"))
+        }
+        random.shuffle(&mut buses);
+        let departure = random.between(1_000_000, 1_010_000);
+        let (wait, earliest) = buses.iter().map(|bus| (bus - departure % bus, *bus)).min().unwrap_or((0, 0));
+        let (mut fields, mut time, mut step) = (vec![], 0u128, 1u128);
+        for bus in buses {
+            if !fields.is_empty() {
+                fields.extend((0..random.between(0, 10)).map(|_| String::from("x")));
+            }
+            // the bus departs at the time plus its offset, so the time is the negative offset modulo the bus
+            let offset = fields.len() as u128;
+            let bus = bus as u128;
+            let remainder = (bus - offset % bus) % bus;
+            // the inverse of the step by Fermat's little theorem, as the step is not a multiple of the prime bus
+            let mut inverse = 1;
+            for _ in 0..bus - 2 {
+                inverse = inverse * (step % bus) % bus;
+            }
+            time += step * ((remainder + bus - time % bus) % bus * inverse % bus);
+            step *= bus;
+            fields.push(bus.to_string());
+        }
+        let input = format!("{}\n{}\n", departure, fields.join(","));
+        Ok(Generated::new(input).plant(Part::One, earliest * wait).plant(Part::Two, time))
+    }
+}
+
+/// Earliest departure and the buses in service
+#[derive(Debug)]
+pub struct Timetable {
+    pub departure: u64,
+    pub buses: Vec<Bus>,
+}
+
+/// Read the timetable from `lines` such as `939` and `7,13,x,x,59,x,31,19`
+pub fn parse_timetable(lines: &[String]) -> Result<Timetable, AocError> {
+    let (first, second) = match lines {
+        [first, second, ..] => (first, second),
+        _ => return Err(AocError::invalid("expected a line of departure and a line of buses")),
+    };
+    let departure = first.parse().map_err(
+        |_| AocError::parse(first, 1, "invalid departure time").at_line(1, first)
+    )?;
+    let mut buses = vec![];
+    let mut column = 1;
+    for (offset, field) in (0..).zip(second.split(',')) {
+        if field != "x" {
+            match field.parse() {
+                Ok(number) if number > 0 => buses.push(Bus {offset, number}),
+                _ => return Err(AocError::parse(
+                    second, column, format!("expected a bus number or 'x', got '{}'", field)
+                ).at_line(2, second)),
+            }
+        }
+        column += field.len() + 1;
+    }
+    Ok(Timetable {departure, buses})
+}
+
+/// Time to wait and id of the first bus leaving after `departure`
+pub fn find_bus(departure: u64, bus_ids: Vec<u64>) -> Option<(u64, u64)> {
+    bus_ids.iter().map(|bus_id| (bus_id - departure % bus_id, *bus_id)).min()
+}
+
+// Approach:
+// Use the "Chinese Remainder Theorem" https://en.wikipedia.org/wiki/Chinese_remainder_theorem
+// The idea is to match up buses pairwise: Once you have a common time t_{ab} for them, any
+// time t_{ab} + n * lcm(a, b) also matches them. This allows searching the next bus at every
+// time t_{abc} = t_{ab} + n_{abc}*lcm(a, b), which is then stable for t_{abc} + n * lcm(a, b, c).
+// This is doable if you have a lcm = Least Common Multiple function.
+// See https://en.wikipedia.org/wiki/Greatest_common_divisor for algorithms.
+// NB: The input appears to be all primes. In other words, lcm(a, b) == a * b. There is no
+// guarantee for that, so using the pedantic variant.
+#[derive(Debug)]
+pub struct Bus {
+    pub number: u64,
+    pub offset: u64,
+}
+
+/// greatest common divisor for a pair of numbers
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+      gcd(b, a % b)
+    }
+}
+
+/// least common multiple for a pair of numbers
+pub fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
+
+/// Chinese Remainder Theorem: https://en.wikipedia.org/wiki/Chinese_remainder_theorem
+pub fn common_time(buses: &[Bus]) -> u64 {
+    let (mut timestamp, mut step) = match buses.first() {
+        Some(first) => {
+            (0, first.number)
+        },
+        None => (0, 1)
+    };
+    for bus in buses.iter().skip(1) {
+        while (timestamp + bus.offset) % bus.number != 0 {
+            timestamp += step
+        }
+        step = lcm(step, bus.number);
+        verbose!("Day 13 fits bus {} at offset {} from {}, stepping by {}", bus.number, bus.offset, timestamp, step);
+    }
+    timestamp % step
+}
@@ -0,0 +1,123 @@
+use std::str::FromStr;
+use std::io::BufRead;
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Part, Solution};
+use crate::utility::parse_lines;
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 2;
+    type Input = Vec<PolicyPassword>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse_lines(input)
+    }
+
+    /// Number of passwords matching their range policy
+    fn part1(&self, values: &Self::Input) -> Result<usize, AocError> {
+        Ok(values.iter().filter(|p| p.in_range()).count())
+    }
+
+    /// Number of passwords matching their position policy
+    fn part2(&self, values: &Self::Input) -> Result<usize, AocError> {
+        Ok(values.iter().filter(|p| p.in_position()).count())
+    }
+
+    /// Policies and passwords, 1000 by default, each randomly matching either policy or not
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        let letters: Vec<char> = ('a'..='z').collect();
+        let (mut input, mut in_range, mut in_position) = (String::new(), 0, 0);
+        for _ in 0..size.unwrap_or(1000) {
+            let min = random.between(1, 10) as usize;
+            let max = min + random.between(1, 9) as usize;
+            let symbol = *random.choose(&letters);
+            let others: Vec<char> = letters.iter().copied().filter(|letter| *letter != symbol).collect();
+            // leave enough room for a count above `max` besides the two positions
+            let mut password: Vec<char> = (0..max + random.between(4, 8) as usize).map(
+                |_| *random.choose(&others)
+            ).collect();
+            let (at_min, at_max) = (random.chance(50), random.chance(50));
+            password[min - 1] = if at_min {symbol} else {password[min - 1]};
+            password[max - 1] = if at_max {symbol} else {password[max - 1]};
+            let placed = at_min as usize + at_max as usize;
+            let count = match random.chance(50) {
+                true => random.between(min.max(placed) as u64, max as u64) as usize,
+                false if placed < min => random.between(placed as u64, min as u64 - 1) as usize,
+                false => random.between(max as u64 + 1, max as u64 + 3) as usize,
+            };
+            let mut free: Vec<usize> = (0..password.len()).filter(
+                |index| *index != min - 1 && *index != max - 1
+            ).collect();
+            random.shuffle(&mut free);
+            for index in free.into_iter().take(count - placed) {
+                password[index] = symbol;
+            }
+            in_range += (min <= count && count <= max) as usize;
+            in_position += (at_min != at_max) as usize;
+            input += &format!("{}-{} {}: {}\n", min, max, symbol, password.iter().collect::<String>());
+        }
+        Ok(Generated::new(input).plant(Part::One, in_range).plant(Part::Two, in_position))
+    }
+}
+
+#[derive(Debug)]
+pub struct PolicyPassword {
+    pub min: usize,
+    pub max: usize,
+    pub symbol: String,
+    pub password: String,
+}
+
+impl PolicyPassword {
+    /// Whether `symbol` appears between `min` and `max` times
+    pub fn in_range(&self) -> bool {
+        let count = self.password.matches(&self.symbol).count();
+        self.min <= count && count <= self.max
+    }
+
+    /// Whether `symbol` appears either at the `min`'th or `max`'th position
+    pub fn in_position(&self) -> bool {
+        let password_chars: Vec<char> = self.password.chars().collect();
+        let at = |position: usize| position.checked_sub(1)
+            .and_then(|index| password_chars.get(index))
+            .is_some_and(|c| String::from(*c) == self.symbol);
+        at(self.min) ^ at(self.max)
+    }
+}
+
+impl FromStr for PolicyPassword {
+    type Err = AocError;
+
+    /// Parse a string such as `3-7 x: xjxbgpxxgtx`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(' ').collect();
+        if parts.len() != 3 {
+            return Err(AocError::parse(s, 1, "expected a policy such as '3-7 x: xjxbgpxxgtx'"))
+        }
+        let range: Vec<&str> = parts[0].split('-').collect();
+        if range.len() != 2 {
+            return Err(AocError::parse(s, 1, "expected a range such as '3-7'"))
+        }
+        if parts[1].chars().count() != 2 || !parts[1].ends_with(':') {
+            return Err(AocError::parse(s, parts[0].len() + 2, "expected a symbol such as 'x:'"))
+        }
+        let count = |literal: &str, column| literal.parse().map_err(
+            |_| AocError::parse(s, column, format!("invalid count '{}'", literal))
+        );
+        let (min, max) = (count(range[0], 1)?, count(range[1], range[0].len() + 2)?);
+        if min == 0 || min > max {
+            return Err(AocError::parse(s, 1, format!("invalid range '{}', expected 1 <= min <= max", parts[0])))
+        }
+        Ok(PolicyPassword {
+            min,
+            max,
+            symbol: String::from(&parts[1][..parts[1].len() - 1]),
+            password: String::from(parts[2]),
+        })
+    }
+}
\ No newline at end of file
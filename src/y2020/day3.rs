@@ -0,0 +1,111 @@
+use std::str::FromStr;
+use std::io::BufRead;
+use std::fmt;
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Part, Solution};
+use crate::utility::{parse_arguments, parse_lines};
+
+
+/// Steps right and down of the slopes of the second part
+const SLOPES: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 3;
+    type Input = Vec<MapLine>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse_lines(input)
+    }
+
+    fn part1(&self, map: &Self::Input) -> Result<i64, AocError> {
+        Ok(sum_slope(map, 3, 1))
+    }
+
+    fn part2(&self, map: &Self::Input) -> Result<i64, AocError> {
+        Ok(SLOPES.iter().map(
+            |(r, d)| sum_slope(map, *r, *d)
+        ).product::<i64>())
+    }
+
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[("slope RIGHT DOWN", "number of trees hit going RIGHT and DOWN per step")]
+    }
+
+    fn explore(&self, map: &Self::Input, command: &str, arguments: &str) -> Result<String, AocError> {
+        match (command, &parse_arguments(arguments, 2)?[..]) {
+            ("slope", [_, 0]) => Err(AocError::invalid("cannot go down by 0 per step")),
+            ("slope", [right, down]) => Ok(sum_slope(map, *right, *down).to_string()),
+            _ => Err(AocError::invalid(format!("unknown command '{}'", command))),
+        }
+    }
+
+    /// Map of 31 squares wide with a quarter of trees, 323 rows by default
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        let (mut input, mut hits) = (String::new(), [0i64; 5]);
+        for row in 0..size.unwrap_or(323) {
+            let squares: Vec<bool> = (0..31).map(|_| random.chance(25)).collect();
+            for (hit, (right, down)) in hits.iter_mut().zip(SLOPES.iter()) {
+                if row % down == 0 && squares[row / down * right % squares.len()] {
+                    *hit += 1;
+                }
+            }
+            input.extend(squares.iter().map(|tree| if *tree {'#'} else {'.'}));
+            input.push('\n');
+        }
+        let product = hits.iter().try_fold(1i64, |product, hit| product.checked_mul(*hit)).ok_or_else(
+            || AocError::invalid("too many rows for the product of trees hit to fit")
+        )?;
+        Ok(Generated::new(input).plant(Part::One, hits[1]).plant(Part::Two, product))
+    }
+}
+
+/// Number of trees hit going `right` and `down` per step
+pub fn sum_slope(map: &[MapLine], right: usize, down: usize) -> i64 {
+    let mut hits = 0;
+    for (index, line) in map.iter().enumerate() {
+        if let (true, 0) = (line.is_tree(index / down * right), index % down) {
+            hits += 1
+        }
+    };
+    hits
+}
+
+
+#[derive(Debug)]
+pub struct MapLine(pub Vec<bool>);
+
+impl MapLine {
+    /// Whether there is a tree at `i`
+    pub fn is_tree(&self, i: usize) -> bool {
+        self.0[i % self.0.len()]
+    }
+}
+
+impl fmt::Display for MapLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: Vec<&str> = self.0.iter().map(|b| match b {true => "#", _ => "."}).collect();
+        write!(f, "[{}]", fields.join(""))
+    }
+}
+
+impl FromStr for MapLine {
+    type Err = AocError;
+
+    /// Read a literal such as `.........#....#.###.........##.`
+    fn from_str(s: &str)  -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(AocError::parse(s, 1, "expected at least one square"))
+        }
+        s.chars().enumerate().map(|(index, c)| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            c => Err(AocError::parse(s, index + 1, format!("expected '#' or '.', got '{}'", c))),
+        }).collect::<Result<_, _>>().map(MapLine)
+    }
+}
\ No newline at end of file
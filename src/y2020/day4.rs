@@ -0,0 +1,209 @@
+use std::str::FromStr;
+use std::io::BufRead;
+use std::collections::BTreeMap;
+
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Part, Solution};
+use crate::utility::parse_lines;
+
+/// Keys of the fields every passport must have
+const REQUIRED: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+/// Eye colours of valid passports
+const EYE_COLOURS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 4;
+    type Input = Vec<PartialPassport>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        Ok(compile_passports(&parse_lines(input)?))
+    }
+
+    /// Number of passports with all required fields
+    fn part1(&self, passports: &Self::Input) -> Result<usize, AocError> {
+        Ok(passports.iter().filter(|pp| pp.is_complete()).count())
+    }
+
+    /// Number of passports with all required fields holding valid values
+    fn part2(&self, passports: &Self::Input) -> Result<usize, AocError> {
+        Ok(passports.iter().filter(|pp| pp.is_valid()).count())
+    }
+
+    /// Batch of passports, 290 by default, which are valid, have an invalid field or miss a field
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        let (mut batches, mut complete, mut valid) = (vec![], 0, 0);
+        for _ in 0..size.unwrap_or(290) {
+            let mut fields: Vec<(&str, String)> = REQUIRED.iter().map(|key| (*key, field(random, key, true))).collect();
+            match random.between(1, 4) {
+                1 => {
+                    fields.remove(random.below(fields.len()));
+                },
+                2 => {
+                    let index = random.below(fields.len());
+                    fields[index].1 = field(random, fields[index].0, false);
+                    complete += 1;
+                },
+                _ => {
+                    complete += 1;
+                    valid += 1;
+                },
+            }
+            if random.chance(50) {
+                fields.push(("cid", random.between(100, 350).to_string()));
+            }
+            random.shuffle(&mut fields);
+            let mut batch = String::new();
+            for (index, (key, value)) in fields.iter().enumerate() {
+                let separator = if index == 0 {""} else if random.chance(25) {"\n"} else {" "};
+                batch += &format!("{}{}:{}", separator, key, value);
+            }
+            batches.push(batch + "\n");
+        }
+        Ok(Generated::new(batches.join("\n")).plant(Part::One, complete).plant(Part::Two, valid))
+    }
+}
+
+/// Random value of the required field `key`, which is either `valid` or not
+fn field(random: &mut Random, key: &str, valid: bool) -> String {
+    let year = |random: &mut Random, min, max| match valid {
+        true => random.between(min, max),
+        false if random.chance(50) => random.between(min - 20, min - 1),
+        false => random.between(max + 1, max + 20),
+    }.to_string();
+    let hex = |random: &mut Random| format!("{:06x}", random.between(0, 0xff_ffff));
+    match (key, valid) {
+        ("byr", _) => year(random, 1920, 2002),
+        ("iyr", _) => year(random, 2010, 2020),
+        ("eyr", _) => year(random, 2020, 2030),
+        ("hgt", true) if random.chance(50) => format!("{}cm", random.between(150, 193)),
+        ("hgt", true) => format!("{}in", random.between(59, 76)),
+        ("hgt", false) => match random.between(1, 3) {
+            1 => format!("{}cm", random.between(100, 149)),
+            2 => format!("{}in", random.between(77, 99)),
+            _ => random.between(59, 193).to_string(),
+        },
+        ("hcl", true) => format!("#{}", hex(random)),
+        ("hcl", false) if random.chance(50) => hex(random),
+        ("hcl", false) => format!("#{}z", &hex(random)[1..]),
+        ("ecl", true) => String::from(*random.choose(&EYE_COLOURS)),
+        ("ecl", false) => String::from(*random.choose(&["gmt", "xry", "dne", "zzz"])),
+        ("pid", true) => format!("{:09}", random.between(0, 999_999_999)),
+        ("pid", false) if random.chance(50) => format!("{:08}", random.between(0, 99_999_999)),
+        ("pid", false) => format!("{:010}", random.between(0, 9_999_999_999)),
+        _ => unreachable!("unknown passport field '{}'", key),
+    }
+}
+
+/// Merge consecutive passport data
+pub fn compile_passports(partials: &[PartialPassport]) -> Vec<PartialPassport> {
+    let mut compiled = vec![];
+    let mut current = PartialPassport::new();
+    for partial in partials.iter() {
+        if partial.is_empty() && !current.is_empty() {
+            compiled.push(current);
+            current = PartialPassport::new();
+        }
+        else {
+            current = current.merge(partial)
+        }
+    }
+    if !current.is_empty() {
+        compiled.push(current);
+    }
+    compiled
+}
+
+// Helpers for validating fields
+fn is_inrange(literal: &str, min: i32, max: i32) -> bool {
+    match literal.parse::<i32>() {
+        Err(_) => false,
+        Ok(x) => min <= x && x <= max,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PartialPassport (pub BTreeMap<String, String>);
+
+
+impl PartialPassport {
+    pub fn new() -> PartialPassport {
+        PartialPassport(BTreeMap::new())
+    }
+
+    /// Whether any fields are present
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether all required fields are present
+    pub fn is_complete(&self) -> bool {
+        REQUIRED.iter().all(
+            |required_key| self.0.contains_key(*required_key)
+        )
+    }
+
+    /// Whether all required fields hold valid values
+    pub fn is_valid(&self) -> bool {
+        self.is_complete() &&
+            // byr (Birth Year) - four digits; at least 1920 and at most 2002.
+            is_inrange(&self.0["byr"], 1920, 2002) &&
+            // iyr (Issue Year) - four digits; at least 2010 and at most 2020.
+            is_inrange(&self.0["iyr"], 2010, 2020) &&
+            // eyr (Expiration Year) - four digits; at least 2020 and at most 2030.
+            is_inrange(&self.0["eyr"], 2020, 2030) &&
+            // hgt (Height) - a number followed by either cm or in:
+            match &self.0["hgt"].split_at(self.0["hgt"].len().saturating_sub(2)) {
+                // If cm, the number must be at least 150 and at most 193.
+                (x, "cm") => is_inrange(x, 150, 193),
+                // If in, the number must be at least 59 and at most 76.
+                (x, "in") => is_inrange(x, 59, 76),
+                _ => false,
+            } &&
+            // hcl (Hair Color) - a # followed by exactly six characters 0-9 or a-f.
+            self.0["hcl"].starts_with('#') &&
+            self.0["hcl"].len() == 7 &&
+            self.0["hcl"][1..].chars().all(|c| "0123456789abcdef".contains(c)) &&
+            // ecl (Eye Color) - exactly one of: amb blu brn gry grn hzl oth.
+            EYE_COLOURS.iter().any(|color| *color == self.0["ecl"]) &&
+            // pid (Passport ID) - a nine-digit number, including leading zeroes.
+            self.0["pid"].len() == 9 &&
+            is_inrange(&self.0["pid"], 0, 999999999)
+    }
+
+    /// Merge two partial passports to a new one
+    pub fn merge(&self, other: &PartialPassport) -> PartialPassport {
+        let m = self.0.iter().chain(&other.0).map(|(k, v)| (k.clone(), v.clone())).collect();
+        PartialPassport(m)
+    }
+}
+
+
+impl FromStr for PartialPassport {
+    type Err = AocError;
+
+    /// Read a literal such as `hcl:#cfa07d byr:1929`
+    fn from_str(s: &str)  -> Result<Self, Self::Err> {
+        let mut new_self = PartialPassport::new();
+        let mut column = 1;
+        for key_value in s.split(' ') {
+            match key_value.find(':') {
+                _ if key_value.is_empty() => (),
+                Some(i) => {
+                    new_self.0.insert(String::from(&key_value[..i]), String::from(&key_value[i + 1..]));
+                },
+                None => return Err(AocError::parse(
+                    s, column, format!("expected a field such as 'byr:1929', got '{}'", key_value)
+                )),
+            }
+            column += key_value.chars().count() + 1;
+        }
+        Ok(new_self)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+use std::str::FromStr;
+use std::io::BufRead;
+
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Part, Solution};
+use crate::utility::parse_lines;
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 5;
+    /// All seats, sorted by their id
+    type Input = Vec<Seat>;
+    type Answer1 = i16;
+    type Answer2 = i16;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        let mut seats: Vec<Seat> = parse_lines(input)?;
+        seats.sort_by_key(|s| s.id());
+        Ok(seats)
+    }
+
+    /// Highest seat id
+    fn part1(&self, seats: &Self::Input) -> Result<i16, AocError> {
+        seats.last().map(|seat| seat.id()).ok_or_else(|| AocError::invalid("there are no seats"))
+    }
+
+    /// Id of the only free seat between two taken seats
+    fn part2(&self, seats: &Self::Input) -> Result<i16, AocError> {
+        seats.iter().zip(seats.iter().skip(1)).find(
+            |(prev, next)| next.id() - prev.id() == 2
+        ).map(|(_, next)| next.id() - 1).ok_or_else(|| AocError::invalid("there is no free seat between seats"))
+    }
+
+    /// Boarding passes of a block of seats with one free seat inside, 850 by default
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        let size = size.unwrap_or(850) as u64;
+        if !(2..=1023).contains(&size) {
+            return Err(AocError::invalid("the plane fits 2 to 1023 seats around the free seat"))
+        }
+        let first = random.between(0, 1023 - size);
+        let free = random.between(first + 1, first + size - 1);
+        let mut ids: Vec<u64> = (first..=first + size).filter(|id| *id != free).collect();
+        random.shuffle(&mut ids);
+        let mut input = String::new();
+        for id in ids {
+            // the row is the upper seven bits and the column the lower three
+            input.extend(format!("{:010b}", id).chars().enumerate().map(|(index, bit)| match (index < 7, bit) {
+                (true, '1') => 'B',
+                (true, _) => 'F',
+                (false, '1') => 'R',
+                (false, _) => 'L',
+            }));
+            input.push('\n');
+        }
+        Ok(Generated::new(input).plant(Part::One, first + size).plant(Part::Two, free))
+    }
+}
+
+#[derive(Debug)]
+pub struct Seat{
+    pub row: i16,
+    pub column: i16,
+}
+
+
+impl Seat {
+    /// Unique id of the seat
+    pub fn id(&self) -> i16 {
+        self.row * 8 + self.column
+    }
+}
+
+
+
+impl FromStr for Seat {
+    type Err = AocError;
+
+    /// Read a literal such as `BFFFBBFRRR` to `70, 7`
+    fn from_str(s: &str)  -> Result<Self, Self::Err> {
+        for (index, c) in s.chars().enumerate() {
+            match (index, c) {
+                (0..=6, 'F') | (0..=6, 'B') | (7..=9, 'L') | (7..=9, 'R') => (),
+                (0..=6, c) => return Err(AocError::parse(s, index + 1, format!("expected 'F' or 'B', got '{}'", c))),
+                (7..=9, c) => return Err(AocError::parse(s, index + 1, format!("expected 'L' or 'R', got '{}'", c))),
+                _ => return Err(AocError::parse(s, index + 1, "expected 10 characters")),
+            }
+        }
+        if s.chars().count() != 10 {
+            return Err(AocError::parse(s, 1, "expected 10 characters such as 'BFFFBBFRRR'"))
+        }
+        let row = (0..).zip(s.chars().take(7)).map(
+            |(index, fb)| if fb == 'B' {2i16.pow(6 - index)} else {0}
+        ).sum::<i16>();
+        let column = (0..).zip(s.chars().skip(7).take(3)).map(
+            |(index, lr)| if lr == 'R' {2i16.pow(2 - index)} else {0}
+        ).sum::<i16>();
+        Ok(Seat {row, column})
+    }
+}
\ No newline at end of file
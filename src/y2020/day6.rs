@@ -0,0 +1,124 @@
+use std::str::FromStr;
+use std::io::BufRead;
+use std::iter::FromIterator;
+use std::collections::HashSet;
+
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Part, Solution};
+use crate::utility::parse_lines;
+
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 6;
+    type Input = Vec<Questionnaire>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse_lines(input)
+    }
+
+    /// Sum of questions answered by anyone in each group
+    fn part1(&self, questionnaires: &Self::Input) -> Result<usize, AocError> {
+        let groups_any = merge_groups(questionnaires, false);
+        Ok(groups_any.iter().map(|q| q.0.len()).sum::<usize>())
+    }
+
+    /// Sum of questions answered by everyone in each group
+    fn part2(&self, questionnaires: &Self::Input) -> Result<usize, AocError> {
+        let groups_all = merge_groups(questionnaires, true);
+        Ok(groups_all.iter().map(|q| q.0.len()).sum::<usize>())
+    }
+
+    /// Answers of groups of one to five people, 490 groups by default
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        let questions: Vec<char> = ('a'..='z').collect();
+        let (mut groups, mut anyone, mut everyone) = (vec![], 0, 0);
+        for _ in 0..size.unwrap_or(490) {
+            let common: Vec<char> = questions.iter().copied().filter(|_| random.chance(15)).collect();
+            let mut group = String::new();
+            let (mut union, mut intersection) = (HashSet::new(), None::<HashSet<char>>);
+            for _ in 0..random.between(1, 5) {
+                let mut answers: Vec<char> = questions.iter().copied().filter(
+                    |question| common.contains(question) || random.chance(30)
+                ).collect();
+                // an empty line would end the group
+                if answers.is_empty() {
+                    answers.push(*random.choose(&questions));
+                }
+                random.shuffle(&mut answers);
+                group.extend(answers.iter());
+                group.push('\n');
+                let answers: HashSet<char> = answers.into_iter().collect();
+                union.extend(answers.iter().copied());
+                intersection = Some(match intersection {
+                    Some(intersection) => intersection.intersection(&answers).copied().collect(),
+                    None => answers,
+                });
+            }
+            anyone += union.len();
+            everyone += intersection.map_or(0, |intersection| intersection.len());
+            groups.push(group);
+        }
+        Ok(Generated::new(groups.join("\n")).plant(Part::One, anyone).plant(Part::Two, everyone))
+    }
+}
+
+/// Merge consecutive questionnaires, using their `overlap` or union
+pub fn merge_groups(questionnaires: &[Questionnaire], overlap: bool) -> Vec<Questionnaire> {
+    let mut compiled = vec![];
+    let mut current: Option<Questionnaire> = None;
+    for questionnaire in questionnaires.iter() {
+        match (questionnaire.is_empty(), &current) {
+            (true, None) => (),
+            (true, Some(qs)) => {
+                if !qs.is_empty() {compiled.push(current.unwrap().clone())};
+                current = None
+            },
+            (false, None) => current = Some(questionnaire.clone()),
+            (false, Some(qs)) => {
+                current = Some(if overlap {qs.overlap(questionnaire)} else {qs.merge(questionnaire)})
+            }
+        }
+    }
+    if let Some(x) = current {
+        if !x.is_empty() {compiled.push(x)}
+    }
+    compiled
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Questionnaire (pub HashSet<char>);
+
+
+impl Questionnaire {
+    pub fn merge(&self, other: &Questionnaire) -> Questionnaire {
+        Questionnaire(HashSet::from_iter(self.0.iter().chain(other.0.iter()).copied()))
+    }
+
+    pub fn overlap(&self, other: &Questionnaire) -> Questionnaire {
+        let intersection: HashSet<_> = self.0.intersection(&other.0).copied().collect();
+        Questionnaire(intersection)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for Questionnaire {
+    type Err = AocError;
+
+    /// Read a literal such as `abcy`
+    fn from_str(s: &str)  -> Result<Self, Self::Err> {
+        match s.chars().position(|c| !c.is_ascii_lowercase()) {
+            Some(index) => Err(AocError::parse(s, index + 1, "expected questions 'a' to 'z'")),
+            None => Ok(Questionnaire (HashSet::from_iter(s.chars()))),
+        }
+    }
+}
\ No newline at end of file
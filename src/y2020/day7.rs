@@ -0,0 +1,255 @@
+use std::str::FromStr;
+use std::io::BufRead;
+use std::iter::FromIterator;
+use std::collections::{HashMap, HashSet};
+
+use crate::config::Section;
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Part, Solution};
+use crate::utility::{parse_lines, partition};
+
+
+pub struct Puzzle {
+    /// Colour of the bag to look for
+    pub root: String,
+}
+
+impl Default for Puzzle {
+    fn default() -> Self {
+        Puzzle {root: String::from("shiny gold")}
+    }
+}
+
+impl Solution for Puzzle {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 7;
+    type Input = Vec<BagSpec>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse_lines(input)
+    }
+
+    /// Number of bags that may eventually contain a root (shiny gold) bag
+    fn part1(&self, bag_specs: &Self::Input) -> Result<usize, AocError> {
+        let contained: BagContained = bag_specs.iter().cloned().collect();
+        Ok(contained.count_leaves(self.root.clone()))
+    }
+
+    /// Number of bags inside a root (shiny gold) bag
+    fn part2(&self, bag_specs: &Self::Input) -> Result<usize, AocError> {
+        let containing: BagContaining = bag_specs.iter().cloned().collect();
+        Ok(containing.total_contained(&self.root) - 1)
+    }
+
+    fn configure(&mut self, params: &Section) -> Result<(), String> {
+        params.expect_keys(&["root"])?;
+        self.root = params.string("root")?.unwrap_or_else(|| self.root.clone());
+        Ok(())
+    }
+
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("inside COLOUR", "number of bags inside a COLOUR bag"),
+            ("outside COLOUR", "number of bags that may eventually contain a COLOUR bag"),
+        ]
+    }
+
+    fn explore(&self, bag_specs: &Self::Input, command: &str, colour: &str) -> Result<String, AocError> {
+        if !bag_specs.iter().any(|bag_spec| bag_spec.color == colour) {
+            return Err(AocError::invalid(format!("there is no rule for '{}' bags", colour)))
+        }
+        match command {
+            "inside" => {
+                let containing: BagContaining = bag_specs.iter().cloned().collect();
+                Ok((containing.total_contained(colour) - 1).to_string())
+            },
+            "outside" => {
+                let contained: BagContained = bag_specs.iter().cloned().collect();
+                Ok(contained.count_leaves(String::from(colour)).to_string())
+            },
+            _ => Err(AocError::invalid(format!("unknown command '{}'", command))),
+        }
+    }
+
+    /// Rules of as many colours as given, 594 by default, including the root colour
+    ///
+    /// Bags only contain bags of colours later in a random order, so that there are no cycles.
+    /// No bag contains much more than a million bags, which keeps counting them quick.
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        let size = size.unwrap_or(594);
+        let mut colours: Vec<String> = (0..size).map(colour).collect();
+        random.shuffle(&mut colours);
+        if !colours.contains(&self.root) {
+            colours[0] = self.root.clone();
+        }
+        // put the root in the middle, so that it is both contained in and contains bags
+        let root = random.between(size as u64 / 4, size as u64 * 3 / 4) as usize;
+        let at = colours.iter().position(|colour| *colour == self.root).unwrap();
+        colours.swap(at, root);
+        let parents: Vec<usize> = (0..random.between(1, 3)).filter(|_| root > 0).map(|_| random.below(root)).collect();
+        let (mut totals, mut contains_root, mut specs) = (vec![1usize; size], vec![false; size], vec![]);
+        for index in (0..size).rev() {
+            let mut children: Vec<usize> = (0..random.between(0, 4)).filter(|_| index + 1 < size).map(
+                |_| random.between(index as u64 + 1, size as u64 - 1) as usize
+            ).collect();
+            if parents.contains(&index) {
+                children.insert(0, root);
+            }
+            let mut constituents = vec![];
+            for child in children {
+                let count = if child == root {1} else {random.between(1, 5) as usize};
+                let total = totals[index] + count * totals[child];
+                // the chosen parents always contain the root, even beyond the limit
+                let forced = child == root && constituents.is_empty();
+                if (total <= 1_000_000 || forced) && !constituents.iter().any(|(_, other)| *other == colours[child]) {
+                    totals[index] = total;
+                    contains_root[index] |= child == root || contains_root[child];
+                    constituents.push((count, colours[child].clone()));
+                }
+            }
+            specs.push(BagSpec {color: colours[index].clone(), constituents});
+        }
+        let mut rules: Vec<String> = specs.iter().map(|spec| match spec.constituents.is_empty() {
+            true => format!("{} bags contain no other bags.\n", spec.color),
+            false => format!("{} bags contain {}.\n", spec.color, spec.constituents.iter().map(
+                |(count, color)| format!("{} {} bag{}", count, color, if *count == 1 {""} else {"s"})
+            ).collect::<Vec<_>>().join(", ")),
+        }).collect();
+        random.shuffle(&mut rules);
+        let containers = contains_root.iter().filter(|contains| **contains).count();
+        Ok(Generated::new(rules.concat()).plant(Part::One, containers).plant(Part::Two, totals[root] - 1))
+    }
+}
+
+
+/// First words of the generated colours
+const SHADES: [&str; 18] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored", "muted", "pale",
+    "plaid", "posh", "shiny", "striped", "vibrant", "wavy",
+];
+
+/// Second words of the generated colours
+const HUES: [&str; 33] = [
+    "aqua", "beige", "black", "blue", "bronze", "brown", "chartreuse", "coral", "crimson", "cyan", "fuchsia",
+    "gold", "gray", "green", "indigo", "lavender", "lime", "magenta", "maroon", "olive", "orange", "plum",
+    "purple", "red", "salmon", "silver", "tan", "teal", "tomato", "turquoise", "violet", "white", "yellow",
+];
+
+/// Distinct colour of each `index`, such as `shiny gold`, with a number once all pairs of words are used
+fn colour(index: usize) -> String {
+    let (shade, hue) = (index % SHADES.len(), index / SHADES.len() % HUES.len());
+    match index / (SHADES.len() * HUES.len()) {
+        0 => format!("{} {}", SHADES[shade], HUES[hue]),
+        round => format!("{}{} {}", SHADES[shade], round + 1, HUES[hue]),
+    }
+}
+
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct BagContained (pub HashMap<String, Vec<String>>);
+
+impl FromIterator<BagSpec> for BagContained {
+    fn from_iter<I: IntoIterator<Item=BagSpec>>(iter: I) -> Self {
+        let mut map = HashMap::<String, Vec<String>>::new();
+        for bag_spec in iter {
+            for (_, child) in bag_spec.constituents {
+                map.entry(child).or_default().push(bag_spec.color.clone())
+            }
+        }
+        BagContained (map)
+    }
+}
+
+impl BagContained {
+    fn collect_leaves(&self, root: &str, seen: &mut HashSet<String>) {
+        if !seen.contains(root) {
+            seen.insert(String::from(root));
+            if let Some(nodes) = self.0.get(root) {
+                for node in nodes {
+                    self.collect_leaves(node, seen)
+                }
+            }
+        }
+    }
+
+    /// Number of bags that may eventually contain the `root` bag
+    pub fn count_leaves(&self, root: String) -> usize {
+        let mut seen = HashSet::new();
+        self.collect_leaves(&root, &mut seen);
+        seen.len() - 1
+    }
+}
+
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct BagContaining (pub HashMap<String, BagSpec>);
+
+impl FromIterator<BagSpec> for BagContaining {
+    fn from_iter<I: IntoIterator<Item=BagSpec>>(iter: I) -> Self {
+        BagContaining (iter.into_iter().map(|bs| (bs.color.clone(), bs)).collect())
+    }
+}
+
+impl BagContaining {
+    /// Number of bags inside the `root` bag, including itself
+    pub fn total_contained(&self, root: &str) -> usize {
+        match self.0.get(root) {
+            Some(bag_spec) => 1usize + bag_spec.constituents.iter().map(
+                |(count, color)| count * self.total_contained(color)
+            ).sum::<usize>(),
+            None => 1,
+        }
+    }
+}
+
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct BagSpec {
+    pub color: String,
+    pub constituents: Vec<(usize, String)>,
+}
+
+/// Parse the constituents at the `tail` of a bag specification `line`
+fn parse_constituents(line: &str, tail: String) -> Result<Vec<(usize, String)>, AocError> {
+    let mut constituents = vec![];
+    let mut column = line.len() - tail.len() + 1;
+    for part in tail.split(',') {
+        // " 2 wavy olive bags." => (" 2 wavy olive", "s.")
+        let (counted_color, _) = partition(part, " bag");
+        // " 2 wavy olive" => ("2", "wavy olive")
+        let (count, color) = partition(counted_color.trim(), " ");
+        match (count.parse::<usize>(), color) {
+            (Ok(i), Some(c)) => constituents.push((i, c)),
+            _ => return Err(AocError::parse(
+                line, column + part.len() - part.trim_start().len(),
+                format!("expected bags such as '2 wavy olive bags', got '{}'", part.trim())
+            )),
+        }
+        column += part.len() + 1;
+    }
+    Ok(constituents)
+}
+
+impl FromStr for BagSpec {
+    type Err = AocError;
+
+    /// Read a literal such as
+    /// `clear chartreuse bags contain 3 dotted black bags, 2 wavy olive bags.`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (color, tail) = partition(s, " bags contain ");
+        match tail {
+            Some(x) => if x == "no other bags." {
+                Ok(BagSpec{color, constituents: vec![]})
+            } else {
+                Ok(BagSpec{constituents: parse_constituents(s, x)?, color})
+            },
+            None => Err(AocError::parse(s, 1, "expected a rule such as 'clear chartreuse bags contain ...'")),
+        }
+    }
+}
\ No newline at end of file
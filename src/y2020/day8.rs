@@ -0,0 +1,201 @@
+use std::io::BufRead;
+use std::str::FromStr;
+use std::collections::HashSet;
+
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Part, Solution};
+use crate::utility::{partition, parse_arguments, parse_lines};
+
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 8;
+    type Input = Vec<Instruction>;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse_lines(input)
+    }
+
+    /// Accumulator value before any instruction is repeated
+    fn part1(&self, instructions: &Self::Input) -> Result<isize, AocError> {
+        let (total, _) = interpret_once(instructions);
+        Ok(total)
+    }
+
+    /// Accumulator value after swapping the JMP/NOP instruction that makes the program terminate
+    fn part2(&self, instructions: &Self::Input) -> Result<isize, AocError> {
+        for (index, instruction) in instructions.iter().enumerate().rev() {
+            match instruction.operation {
+                Operation::JMP | Operation::NOP => {
+                    if let (total, true) = interpret_once(&swap_instruction(instructions, index)) {
+                        return Ok(total)
+                    }
+                },
+                Operation::ACC => (),
+            }
+        }
+        Err(AocError::invalid("no single swap of JMP and NOP makes the program terminate"))
+    }
+
+    fn commands(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("run", "accumulator value when the program terminates or repeats an instruction"),
+            ("swap INDEX", "the same after swapping the JMP or NOP instruction at INDEX, counting from 0"),
+        ]
+    }
+
+    fn explore(&self, instructions: &Self::Input, command: &str, arguments: &str) -> Result<String, AocError> {
+        let program = match command {
+            "run" => {
+                parse_arguments::<usize>(arguments, 0)?;
+                instructions.clone()
+            },
+            "swap" => match parse_arguments::<usize>(arguments, 1)?[0] {
+                index if index >= instructions.len() => return Err(AocError::invalid(format!(
+                    "there are only {} instructions", instructions.len()
+                ))),
+                index => match instructions[index].operation {
+                    Operation::ACC => return Err(AocError::invalid(format!("instruction {} is ACC", index))),
+                    _ => swap_instruction(instructions, index),
+                },
+            },
+            _ => return Err(AocError::invalid(format!("unknown command '{}'", command))),
+        };
+        match interpret_once(&program) {
+            (total, true) => Ok(format!("{} (terminated)", total)),
+            (total, false) => Ok(format!("{} (repeated an instruction)", total)),
+        }
+    }
+
+    /// Program of as many instructions as given, 600 by default, which one swap makes terminate
+    ///
+    /// The program runs through its first half, skipping some dead code, until it jumps back.
+    /// Only not taking that jump leads on to the second half and the end. NOP instructions which are
+    /// run only point backwards, so swapping them or any skipping jump leads back into the loop.
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        let size = size.unwrap_or(600);
+        if size < 3 {
+            return Err(AocError::invalid("need at least 3 instructions"))
+        }
+        let looping = random.between(size as u64 / 2, size as u64 - 2) as usize;
+        let (mut program, mut visited, mut before) = (vec![], vec![], 0);
+        while program.len() < looping {
+            let index = program.len();
+            if looping - index >= 2 && random.chance(15) {
+                let skip = random.between(2, (looping - index).min(6) as u64) as usize;
+                program.push(Instruction {operation: Operation::JMP, argument: skip as isize});
+                visited.push(index);
+                while program.len() < index + skip {
+                    program.push(backwards(random, program.len()));
+                }
+                continue
+            }
+            let instruction = backwards(random, index);
+            if let Operation::ACC = instruction.operation {
+                before += instruction.argument;
+            }
+            program.push(instruction);
+            visited.push(index);
+        }
+        let target = visited.get(random.below(visited.len().max(1))).copied().unwrap_or(looping);
+        program.push(Instruction {operation: Operation::JMP, argument: target as isize - looping as isize});
+        let mut after = before;
+        while program.len() < size {
+            let instruction = match random.chance(50) {
+                true => Instruction {operation: Operation::ACC, argument: random.between(0, 100) as isize - 50},
+                false => Instruction {operation: Operation::NOP, argument: random.between(0, 100) as isize - 50},
+            };
+            if let Operation::ACC = instruction.operation {
+                after += instruction.argument;
+            }
+            program.push(instruction);
+        }
+        let input = program.iter().map(|instruction| format!(
+            "{} {:+}\n", format!("{:?}", instruction.operation).to_lowercase(), instruction.argument
+        )).collect();
+        Ok(Generated::new(input).plant(Part::One, before).plant(Part::Two, after))
+    }
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum Operation {NOP, ACC, JMP}
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct Instruction {
+    pub operation: Operation,
+    pub argument: isize,
+}
+
+impl FromStr for Instruction {
+    type Err = AocError;
+
+    /// Read a literal such as  `jmp +4`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (op, arg) = partition(s, " ");
+        let operation = match op.as_str() {
+            "acc" => Operation::ACC,
+            "jmp" => Operation::JMP,
+            "nop" => Operation::NOP,
+            op => return Err(AocError::parse(s, 1, format!("unknown operation '{}'", op))),
+        };
+        let argument = match arg {
+            Some(x) => x.parse::<isize>().map_err(
+                |_| AocError::parse(s, op.len() + 2, format!("invalid argument '{}'", x))
+            )?,
+            _ => 0,
+        };
+        Ok(Instruction {operation, argument})
+    }
+}
+
+/// Random ACC or NOP instruction at `index`, where the NOP would jump backwards if swapped
+fn backwards(random: &mut Random, index: usize) -> Instruction {
+    match random.chance(50) {
+        true => Instruction {operation: Operation::ACC, argument: random.between(0, 100) as isize - 50},
+        false => Instruction {operation: Operation::NOP, argument: -(random.between(0, index as u64) as isize)},
+    }
+}
+
+/// Run `instructions` until hitting a loop or terminating
+/// Return the accumulated value and whether termination was proper.
+pub fn interpret_once(instructions: &[Instruction]) -> (isize, bool) {
+    let mut pointer: isize = 0;
+    let mut accumulator = 0;
+    let mut seen = HashSet::new();
+    while !seen.contains(&pointer) && (pointer as usize) < instructions.len() {
+        seen.insert(pointer);
+        match &instructions[pointer as usize] {
+            Instruction {operation: Operation::JMP, argument: x} => pointer += x,
+            Instruction {operation: Operation::ACC, argument: x} => {
+                accumulator += x; pointer += 1;
+            },
+            Instruction {operation: Operation::NOP, argument: _} => pointer += 1,
+        }
+    }
+    (accumulator, (pointer as usize) >= instructions.len())
+}
+
+/// Generate new `instructions` by swapping a JMP/NOP instruction at `index`
+pub fn swap_instruction(instructions: &[Instruction], index: usize) -> Vec<Instruction> {
+    let mut new_instructions = instructions.to_vec();
+    match new_instructions[index] {
+        Instruction {operation: Operation::ACC, argument: _} => {
+            panic!("Can only swap JMP and NOP operations!");
+        },
+        Instruction {operation: Operation::JMP, argument} => {
+            new_instructions[index] = Instruction {operation: Operation::NOP, argument}
+        },
+        Instruction {operation: Operation::NOP, argument} => {
+            new_instructions[index] = Instruction {operation: Operation::JMP, argument}
+        },
+    };
+    new_instructions
+}
\ No newline at end of file
@@ -0,0 +1,178 @@
+use std::io::BufRead;
+use std::collections::{VecDeque, HashSet};
+
+use crate::config::Section;
+use crate::error::AocError;
+use crate::generate::{Generated, Random};
+use crate::solution::{Part, Solution};
+use crate::utility::parse_lines;
+
+
+pub struct Puzzle {
+    /// Number of preceding items which may sum to an item
+    pub preamble: usize,
+}
+
+impl Default for Puzzle {
+    fn default() -> Self {
+        Puzzle {preamble: 25}
+    }
+}
+
+impl Solution for Puzzle {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 9;
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &mut dyn BufRead) -> Result<Self::Input, AocError> {
+        parse_lines(input)
+    }
+
+    /// First item that is not the sum of two of the preamble of preceding items
+    fn part1(&self, series: &Self::Input) -> Result<i64, AocError> {
+        find_nonsum(series, self.preamble).ok_or_else(|| AocError::invalid("every item is the sum of two preceding items"))
+    }
+
+    /// Sum of the smallest and largest item of a consecutive window summing to the outlier
+    fn part2(&self, series: &Self::Input) -> Result<i64, AocError> {
+        let outlier = self.part1(series)?;
+        find_sum(series, &outlier).map(|(min, max)| min + max).ok_or_else(
+            || AocError::invalid(format!("no consecutive items sum to {}", outlier))
+        )
+    }
+
+    fn configure(&mut self, params: &Section) -> Result<(), String> {
+        params.expect_keys(&["preamble"])?;
+        self.preamble = params.positive("preamble")?.unwrap_or(self.preamble);
+        Ok(())
+    }
+
+    /// Series of as many items as given, 1000 by default, with an outlier in the second half
+    ///
+    /// Each item but the outlier sums two of the smallest items before it, so that the series grows slowly.
+    /// The outlier sums a consecutive window of earlier items, and is planted where no other window sums to it.
+    fn generate(&self, random: &mut Random, size: Option<usize>) -> Result<Generated, AocError> {
+        let size = size.unwrap_or(1000);
+        if self.preamble < 2 || size <= self.preamble {
+            return Err(AocError::invalid("need a preamble of at least 2 items and more items than the preamble"))
+        }
+        let mut series: Vec<i64> = (1..=2 * self.preamble as i64).collect();
+        random.shuffle(&mut series);
+        series.truncate(self.preamble);
+        let position = random.between(self.preamble.max(size / 2) as u64, size as u64 - 1) as usize;
+        while series.len() < position {
+            series.push(next_sum(random, &series[series.len() - self.preamble..])?);
+        }
+        let mut planted = None;
+        for _ in 0..1000 {
+            let length = random.between(2, position.min(17) as u64) as usize;
+            let start = random.below(position - length + 1);
+            let window = &series[start..start + length];
+            let outlier = window.iter().sum::<i64>();
+            let preceding = &series[position - self.preamble..];
+            let is_sum = preceding.iter().any(|a| preceding.iter().any(|b| a != b && a + b == outlier));
+            if !is_sum && count_sums(&series, outlier) == 1 {
+                planted = Some((outlier, window.iter().min().unwrap() + window.iter().max().unwrap()));
+                break
+            }
+        }
+        let (outlier, weakness) = planted.ok_or_else(
+            || AocError::invalid("found no outlier summing a single window, try another seed")
+        )?;
+        series.push(outlier);
+        while series.len() < size {
+            series.push(next_sum(random, &series[series.len() - self.preamble..])?);
+        }
+        let input = series.iter().map(|item| format!("{}\n", item)).collect();
+        Ok(Generated::new(input).plant(Part::One, outlier).plant(Part::Two, weakness))
+    }
+}
+
+/// Random sum of two different items among the smallest of the `preceding` items
+fn next_sum(random: &mut Random, preceding: &[i64]) -> Result<i64, AocError> {
+    let mut smallest = preceding.to_vec();
+    smallest.sort_unstable();
+    smallest.dedup();
+    smallest.truncate(6);
+    if smallest.len() < 2 {
+        return Err(AocError::invalid("the preceding items are all the same"))
+    }
+    let first = random.below(smallest.len());
+    let second = (first + 1 + random.below(smallest.len() - 1)) % smallest.len();
+    smallest[first].checked_add(smallest[second]).ok_or_else(
+        || AocError::invalid("the items outgrow 64 bits, use fewer items or a longer preamble")
+    )
+}
+
+/// Number of consecutive windows of at least two positive items in `series` summing to `total`
+fn count_sums(series: &[i64], total: i64) -> usize {
+    let mut count = 0;
+    for start in 0..series.len() {
+        let mut sum = series[start];
+        for item in &series[start + 1..] {
+            sum += item;
+            if sum > total {
+                break
+            }
+            count += (sum == total) as usize;
+        }
+    }
+    count
+}
+
+/*
+Both problems ("item not the sum of previous 25 items" and "items with sum of outlier")
+come down to finding a consecutive window in the input series. Thus, the core of both
+solutions is a sliding window, growing for each new item and removing the oldest item(s).
+
+In general, the approach is "add new, pop until condition". In the first case, that condition
+is "``window.len() == window_size``" aka we always pop for each push. In the second case, that
+condition is "``window.sum() <= total``"; this means remove elements until adding any element
+might solve the problem.
+*/
+
+
+/// First item that is not the sum of two items in the `window_size` items before it
+pub fn find_nonsum(series: &[i64], window_size: usize) -> Option<i64> {
+    let mut window: VecDeque<i64> = series.iter().take(window_size).cloned().collect();
+    for item in series.iter().skip(window_size) {
+        let current_window: HashSet<i64> = window.iter().cloned().collect();
+        if current_window.iter().any(
+            |part1| current_window.contains(&(item - part1)) && &(item - part1) != part1
+        ) {
+            window.pop_front();
+            window.push_back(*item);
+        }
+        else {
+            return Some(*item);
+        }
+    }
+    None
+}
+
+/// Smallest and largest item of a consecutive window summing to `total`
+pub fn find_sum(series: &[i64], total: &i64) -> Option<(i64, i64)> {
+    let mut window: VecDeque<i64> = series.iter().take(1).cloned().collect();
+    let mut current_sum: i64;
+    for item in series.iter().skip(1) {
+        window.push_back(*item);
+        current_sum = window.iter().sum();
+        while &current_sum > total {
+            if let Some(x) = window.pop_front(){
+                current_sum -= x;
+            }
+            else {
+                // only happens if total is negative
+                return None;
+            }
+        }
+        if &current_sum == total && window.len() >= 2 {
+            // return the largest and smallest member
+            window.make_contiguous().sort();
+            return Some((*window.front().unwrap(), *window.back().unwrap()));
+        }
+    }
+    None
+}
@@ -0,0 +1,35 @@
+//! Solutions for Advent of Code 2020
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+
+use crate::solution::Registry;
+
+
+/// Add all days of the year with a solution to the `registry`
+pub fn register(registry: &mut Registry) {
+    registry
+        .register(day1::Puzzle::default())
+        .register(day2::Puzzle)
+        .register(day3::Puzzle)
+        .register(day4::Puzzle)
+        .register(day5::Puzzle)
+        .register(day6::Puzzle)
+        .register(day7::Puzzle::default())
+        .register(day8::Puzzle)
+        .register(day9::Puzzle::default())
+        .register(day10::Puzzle)
+        .register(day11::Puzzle)
+        .register(day12::Puzzle)
+        .register(day13::Puzzle);
+}